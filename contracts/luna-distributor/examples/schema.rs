@@ -4,8 +4,13 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use luna_distributor::msg::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, WeightPerProtocol, Whitelist,
+    AdminInfoResponse, ApplicationsResponse, CapUsageResponse, ConfigChange, ConfigHistoryResponse,
+    ContributionsResponse, DeveloperVestingResponse, EpochResponse, ExecuteMsg,
+    GroupMembersResponse, IbcEscrowResponse, InstantiateMsg, MigrateMsg, PausedResponse, QueryMsg,
+    SudoMsg, WeightPerProtocol, WeightPerProtocolResponse, WeightProposalResponse,
+    WeightProposalsResponse, Whitelist, WhitelistResponse,
 };
+use luna_distributor::state::{Ballot, Config, PendingConfigChange, PendingDistribution};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -17,6 +22,26 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(SudoMsg), &out_dir);
     export_schema(&schema_for!(Whitelist), &out_dir);
     export_schema(&schema_for!(WeightPerProtocol), &out_dir);
+    export_schema(&schema_for!(ConfigChange), &out_dir);
+    export_schema(&schema_for!(Config), &out_dir);
+    export_schema(&schema_for!(PendingConfigChange), &out_dir);
+    export_schema(&schema_for!(PendingDistribution), &out_dir);
+    export_schema(&schema_for!(Ballot), &out_dir);
+    export_schema(&schema_for!(AdminInfoResponse), &out_dir);
+    export_schema(&schema_for!(PausedResponse), &out_dir);
+    export_schema(&schema_for!(ConfigHistoryResponse), &out_dir);
+    export_schema(&schema_for!(EpochResponse), &out_dir);
+    export_schema(&schema_for!(DeveloperVestingResponse), &out_dir);
+    export_schema(&schema_for!(ApplicationsResponse), &out_dir);
+    export_schema(&schema_for!(CapUsageResponse), &out_dir);
+    export_schema(&schema_for!(GroupMembersResponse), &out_dir);
+    export_schema(&schema_for!(ContributionsResponse), &out_dir);
+    export_schema(&schema_for!(IbcEscrowResponse), &out_dir);
+    export_schema(&schema_for!(WeightProposalResponse), &out_dir);
+    export_schema(&schema_for!(WeightProposalsResponse), &out_dir);
+    export_schema(&schema_for!(WhitelistResponse), &out_dir);
+    export_schema(&schema_for!(WeightPerProtocolResponse), &out_dir);
 }
@@ -0,0 +1,25 @@
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgFundCommunityPool;
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg};
+use prost::Message;
+use terra_cosmwasm::TerraMsgWrapper;
+
+/// `cosmwasm_std::DistributionMsg` doesn't support funding the community pool,
+/// so `MsgFundCommunityPool` is sent as a stargate message
+const FUND_COMMUNITY_POOL_TYPE_URL: &str = "/cosmos.distribution.v1beta1.MsgFundCommunityPool";
+
+/// Creates message sending `amount` from `depositor` to the community pool
+pub fn fund_community_pool_msg(depositor: &Addr, amount: Coin) -> CosmosMsg<TerraMsgWrapper> {
+    let msg = MsgFundCommunityPool {
+        amount: vec![ProtoCoin {
+            denom: amount.denom,
+            amount: amount.amount.to_string(),
+        }],
+        depositor: depositor.to_string(),
+    };
+
+    CosmosMsg::Stargate {
+        type_url: FUND_COMMUNITY_POOL_TYPE_URL.to_owned(),
+        value: Binary(msg.encode_to_vec()),
+    }
+}
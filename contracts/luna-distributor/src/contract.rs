@@ -1,19 +1,62 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, BankMsg, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    SubMsg, Uint128,
+    to_binary, Api, BalanceResponse, BankMsg, BankQuery, Binary, Decimal, Deps, DepsMut,
+    DistributionMsg, Env, IbcMsg, IbcTimeout, MessageInfo, Order, QueryRequest, Reply, StakingMsg,
+    StdError, StdResult, Storage, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::U64Key;
+use terra_cosmwasm::{create_swap_msg, TerraMsgWrapper};
 
+use crate::community_pool::fund_community_pool_msg;
+use crate::dex;
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, WeightPerProtocol, Whitelist};
-use crate::state::{Config, CONFIG};
+use crate::msg::{
+    AdminInfoResponse, AdminType, ApplicationApproval, ApplicationsResponse, CapUsageEntry,
+    CapUsageResponse, ConfigChange, ConfigHistoryResponse, ContributionsResponse,
+    DeveloperVestingBalance, DeveloperVestingResponse, EpochResponse, EscrowedIbcTransfer,
+    ExecuteMsg, GroupMembersResponse, IbcEscrowResponse, IbcLifecycleComplete, InstantiateMsg,
+    MigrateMsg, PausedResponse, QueryMsg, SudoMsg, WeightPerProtocolResponse,
+    WeightProposalResponse, WeightProposalsResponse, WhitelistResponse,
+};
+use crate::state::{
+    contributions, load_weights, load_whitelist, replace_weights, replace_whitelist, save_weight,
+    save_whitelist_entry, whitelist, Application, ApplicationStatus, Ballot, BurnStrategy,
+    CapOverflow, CapPayout, CapTarget, CapUsage, Config, ConfigRevision, Contribution, Destination,
+    Epoch, FieldChange, GroupSnapshot, IbcTransfer, PendingBuyback, PendingConfigChange,
+    PendingDistribution, PendingSwap, ProposalStatus, QueuedPayout, RecipientCap, SwapPair,
+    VestingBalance, Vote, Voter, VoterSet, WeightPerProtocol, WeightProposal, WeightVoting,
+    WeightedBurnSink, Whitelist, APPLICATIONS, APPLICATION_COUNT, CAP_USAGE, CONFIG,
+    CONFIG_HISTORY, CONFIG_REVISION_COUNT, CONFIG_V0_1, DEVELOPER_VESTING, EPOCHS,
+    GOVERNANCE_PAUSED, GROUP_MEMBERS, GROUP_SNAPSHOT, IBC_ESCROW, IBC_ESCROW_COUNT,
+    IBC_ESCROW_TOTAL, IBC_PACKETS, IBC_TRANSFERS_IN_FLIGHT, PAUSED, PENDING_ADDRESS_CHANGES,
+    PENDING_BUYBACK, PENDING_CONFIG_CHANGE, PENDING_DISTRIBUTION, PENDING_SWAP, QUEUED_PAYOUTS,
+    WEIGHTS, WEIGHT_BALLOTS, WEIGHT_PROPOSALS, WEIGHT_PROPOSAL_COUNT,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:luna-distributor";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Thresholds are expressed in LUNC unless configured otherwise
+const DEFAULT_THRESHOLD_DENOM: &str = "uluna";
+/// 1 LUNC == 1_000_000 uluna
+const DEFAULT_LESS_THEN_THRESHOLD: u128 = 1_000_000;
+/// Max spread of swaps on DEX pairs, 1%
+const DEFAULT_MAX_SPREAD: u64 = 1;
+
+/// Responses carry Terra specific messages, as swaps on the Terra market
+type Response = cosmwasm_std::Response<TerraMsgWrapper>;
+type SubMsg = cosmwasm_std::SubMsg<TerraMsgWrapper>;
+
+const IBC_TRANSFER_REPLY_ID: u64 = 1;
+const SWAP_REPLY_ID: u64 = 2;
+const BUYBACK_REPLY_ID: u64 = 3;
+
+/// Number of members queried from the whitelist group at once
+const GROUP_PAGE_SIZE: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -21,21 +64,9 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    let mut whitelist: Vec<crate::state::Whitelist> = Vec::new();
-    for entry in msg.whitelist {
-        let address = deps.api.addr_validate(&entry.address)?;
-        whitelist.push(crate::state::Whitelist {
-            address,
-            protocol: entry.protocol.clone(),
-        });
-    }
-    let mut weight_per_protocol: Vec<crate::state::WeightPerProtocol> = Vec::new();
-    for entry in msg.weight_per_protocol {
-        weight_per_protocol.push(crate::state::WeightPerProtocol {
-            protocol: entry.protocol.clone(),
-            weight: entry.weight,
-        });
-    }
+    let whitelist = validate_whitelist(deps.api, msg.whitelist)?;
+    replace_whitelist(deps.storage, whitelist)?;
+    replace_weights(deps.storage, convert_weights(msg.weight_per_protocol))?;
 
     let burn_address = deps.api.addr_validate(&msg.burn_address)?;
     let developer_address = deps.api.addr_validate(&msg.developer_address)?;
@@ -43,12 +74,30 @@ pub fn instantiate(
         admin: msg.admin,
         burn_address,
         developer_address,
-        whitelist,
-        weight_per_protocol,
         percent_to_burn: Decimal::percent(70),
         percent_to_developer: Decimal::percent(10),
         percent_to_distribute: Decimal::percent(20),
-        less_then_threshold: Uint128::new(1_000_000u128), // 1 LUNC == 1_000_000 uluna
+        less_then_threshold: Uint128::new(DEFAULT_LESS_THEN_THRESHOLD),
+        threshold_denom: DEFAULT_THRESHOLD_DENOM.to_owned(),
+        // no minimal distribution, as before it was introduced
+        min_distribution: Uint128::zero(),
+        config_change_delay: msg.config_change_delay,
+        epoch_length: 0,
+        developer_vesting: None,
+        developer_destination: None,
+        burn_sinks: vec![],
+        recipient_caps: vec![],
+        cap_window: 0,
+        cap_overflow: CapOverflow::Burn,
+        address_change_requires_accept: false,
+        weight_voting: None,
+        whitelist_group: None,
+        deposit_denoms: vec![],
+        distribution_batch_size: 0,
+        payout_denom: None,
+        swap_pairs: vec![],
+        max_spread: Decimal::percent(DEFAULT_MAX_SPREAD),
+        burn_strategy: BurnStrategy::Direct {},
     };
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     CONFIG.save(deps.storage, &config)?;
@@ -63,6 +112,19 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // Paying out is stopped while paused, config can still be fixed
+    if matches!(
+        msg,
+        ExecuteMsg::Distribute { .. }
+            | ExecuteMsg::BurnTheBottom { .. }
+            | ExecuteMsg::ClaimDeveloperVested {}
+            | ExecuteMsg::RetryIbcTransfer { .. }
+            | ExecuteMsg::ContinueDistribution { .. }
+    ) && is_paused(deps.storage)?
+    {
+        return Err(ContractError::Paused {});
+    }
+
     match msg {
         ExecuteMsg::Distribute { denom } => execute::distribute(deps, env, denom),
         ExecuteMsg::UpdateConfig {
@@ -73,21 +135,214 @@ pub fn execute(
             weight_per_protocol,
         } => execute::update_config(
             deps,
+            env,
             info,
-            admin,
-            burn_address,
-            developer_address,
-            whitelist,
-            weight_per_protocol,
+            ConfigChange {
+                admin,
+                burn_address,
+                developer_address,
+                whitelist,
+                weight_per_protocol,
+                ..ConfigChange::default()
+            },
         ),
         ExecuteMsg::BurnTheBottom { denom } => execute::burn_the_bottom(deps, env, denom),
+        ExecuteMsg::ProposeConfigChange { change } => {
+            execute::propose_config_change(deps, env, info, *change)
+        }
+        ExecuteMsg::ExecuteConfigChange {} => execute::execute_config_change(deps, env, info),
+        ExecuteMsg::CancelConfigChange {} => execute::cancel_config_change(deps, info),
+        ExecuteMsg::ClaimDeveloperVested {} => execute::claim_developer_vested(deps, env, info),
+        ExecuteMsg::ClaimStakingRewards {} => execute::claim_staking_rewards(deps, env),
+        ExecuteMsg::RetryIbcTransfer { id } => execute::retry_ibc_transfer(deps, env, id),
+        ExecuteMsg::ApplyForWhitelist { protocol, address } => {
+            execute::apply_for_whitelist(deps, env, info, protocol, address)
+        }
+        ExecuteMsg::ApproveApplication { id, weight } => {
+            execute::approve_application(deps, env, info, id, weight)
+        }
+        ExecuteMsg::RejectApplication { id } => execute::reject_application(deps, info, id),
+        ExecuteMsg::ChangeMyAddress { new_address } => {
+            execute::change_my_address(deps, env, info, new_address)
+        }
+        ExecuteMsg::AcceptAddressChange { old_address } => {
+            execute::accept_address_change(deps, env, info, old_address)
+        }
+        ExecuteMsg::MemberChangedHook(hook) => execute::member_changed_hook(deps, info, hook),
+        ExecuteMsg::RefreshGroupSnapshot {} => execute::refresh_group_snapshot(deps, env),
+        ExecuteMsg::ProposeWeights { weights } => {
+            execute::propose_weights(deps, env, info, weights)
+        }
+        ExecuteMsg::VoteWeights { proposal_id, vote } => {
+            execute::vote_weights(deps, env, info, proposal_id, vote)
+        }
+        ExecuteMsg::SetPaused { paused } => execute::set_paused(deps, info, paused),
+        ExecuteMsg::Sweep {
+            denom,
+            amount,
+            recipient,
+        } => execute::admin_sweep(deps, env, info, denom, amount, recipient),
+        ExecuteMsg::Deposit {} => execute::deposit(deps, info),
+        ExecuteMsg::ContinueDistribution { limit } => {
+            execute::continue_distribution(deps, env, limit)
+        }
+    }
+}
+
+/// Paying out is stopped if either the admin or governance paused the contract
+fn is_paused(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(PAUSED.may_load(storage)?.unwrap_or_default()
+        || GOVERNANCE_PAUSED.may_load(storage)?.unwrap_or_default())
+}
+
+/// Loads usage of the cap, dropping payouts which are out of the rolling window
+fn load_cap_usage(
+    storage: &dyn Storage,
+    env: &Env,
+    config: &Config,
+    cap: &RecipientCap,
+) -> StdResult<CapUsage> {
+    let mut usage = CAP_USAGE
+        .may_load(storage, (&cap.target.key(), &cap.denom))?
+        .unwrap_or_default();
+    usage
+        .payouts
+        .retain(|payout| env.block.time < payout.time.plus_seconds(config.cap_window));
+    Ok(usage)
+}
+
+/// Returns balance of the contract which is not reserved for vesting developer share,
+/// escrowed IBC transfers, queued protocol payouts or the open epoch
+fn query_available_balance(deps: Deps, env: &Env, denom: &str) -> StdResult<Uint128> {
+    let balance: BalanceResponse = deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
+        address: env.contract.address.to_string(),
+        denom: denom.to_owned(),
+    }))?;
+    let balance = balance.amount.amount;
+    let vesting = DEVELOPER_VESTING
+        .may_load(deps.storage, denom)?
+        .unwrap_or_default();
+    let escrowed = IBC_ESCROW_TOTAL
+        .may_load(deps.storage, denom)?
+        .unwrap_or_default();
+    let queued = PENDING_DISTRIBUTION
+        .may_load(deps.storage)?
+        .filter(|pending| pending.denom == denom)
+        .map(|pending| pending.remaining)
+        .unwrap_or_default();
+    let locked = locked_in_epoch(deps.storage, denom)?;
+    Ok(balance.saturating_sub(vesting.accrued - vesting.claimed + escrowed + queued + locked))
+}
+
+/// Part of the open epoch of `denom` which has not been released yet
+fn locked_in_epoch(storage: &dyn Storage, denom: &str) -> StdResult<Uint128> {
+    Ok(EPOCHS
+        .may_load(storage, denom)?
+        .filter(|epoch| !epoch.is_finished())
+        .map(|epoch| epoch.amount - epoch.released)
+        .unwrap_or_default())
+}
+
+/// Keeps `transfer` on the contract until it is retried. Its amount has to be on the contract.
+fn escrow_ibc_transfer(
+    storage: &mut dyn Storage,
+    transfer: IbcTransfer,
+    error: String,
+) -> Result<Response, ContractError> {
+    let id = IBC_ESCROW_COUNT.may_load(storage)?.unwrap_or_default();
+    IBC_ESCROW_COUNT.save(storage, &(id + 1))?;
+    IBC_ESCROW.save(storage, U64Key::new(id), &transfer)?;
+    IBC_ESCROW_TOTAL.update(storage, &transfer.amount.denom, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() + transfer.amount.amount)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "escrow_ibc_transfer")
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", transfer.amount.to_string())
+        .add_attribute("error", error))
+}
+
+/// Validates whitelist passed in a message. Every address can be whitelisted only once.
+fn validate_whitelist(
+    api: &dyn Api,
+    entries: Vec<crate::msg::Whitelist>,
+) -> Result<Vec<Whitelist>, ContractError> {
+    let mut whitelist: Vec<Whitelist> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let address = api.addr_validate(&entry.address)?;
+        if let Some(existing) = whitelist
+            .iter()
+            .find(|existing| existing.address == address)
+        {
+            return Err(ContractError::AlreadyWhitelisted(
+                address.to_string(),
+                existing.protocol.clone(),
+            ));
+        }
+        whitelist.push(Whitelist {
+            address,
+            protocol: entry.protocol,
+            destination: entry
+                .destination
+                .map(|destination| validate_destination(api, destination))
+                .transpose()?,
+        });
     }
+    Ok(whitelist)
+}
+
+fn convert_weights(weights: Vec<crate::msg::WeightPerProtocol>) -> Vec<WeightPerProtocol> {
+    weights
+        .into_iter()
+        .map(|entry| WeightPerProtocol {
+            protocol: entry.protocol,
+            weight: entry.weight,
+        })
+        .collect()
+}
+
+/// Validates addresses and weights of destination passed in a message
+fn validate_destination(
+    api: &dyn Api,
+    destination: crate::msg::Destination,
+) -> Result<Destination, ContractError> {
+    Ok(match destination {
+        crate::msg::Destination::Address { address } => Destination::Address {
+            address: api.addr_validate(&address)?,
+        },
+        crate::msg::Destination::NativeBurn {} => Destination::NativeBurn {},
+        crate::msg::Destination::CommunityPool {} => Destination::CommunityPool {},
+        crate::msg::Destination::Contract { address, msg } => Destination::Contract {
+            address: api.addr_validate(&address)?,
+            msg,
+        },
+        crate::msg::Destination::Ibc {
+            channel_id,
+            remote_address,
+            timeout_seconds,
+        } => Destination::Ibc {
+            channel_id,
+            remote_address,
+            timeout_seconds,
+        },
+        crate::msg::Destination::Stake { validators } => {
+            let total_weight = validators
+                .iter()
+                .fold(Decimal::zero(), |total, (_, weight)| total + *weight);
+            if validators.is_empty() || total_weight != Decimal::one() {
+                return Err(ContractError::InvalidValidatorWeights {});
+            }
+            Destination::Stake { validators }
+        }
+    })
 }
 
 mod execute {
     use super::*;
 
-    use cosmwasm_std::{coin, BalanceResponse, BankQuery, Coin, QuerierWrapper, QueryRequest};
+    use cosmwasm_std::{coin, Addr, Api, Coin, Event, QuerierWrapper};
+    use cw4::{Cw4Contract, MemberChangedHookMsg};
     use terra_cosmwasm::TerraQuerier;
 
     /// Decimal points
@@ -124,171 +379,2564 @@ mod execute {
         })
     }
 
+    /// Creates bank message sending `amount` to `recipient` with tax deducted.
+    /// Returns the message together with the amount of tax paid.
+    pub fn send_with_tax(
+        querier: &QuerierWrapper,
+        recipient: &Addr,
+        amount: Coin,
+    ) -> StdResult<(SubMsg, Uint128)> {
+        let sent = deduct_tax(&amount, querier)?;
+        let tax = amount.amount - sent.amount;
+        let msg = SubMsg::new(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![sent],
+        });
+        Ok((msg, tax))
+    }
+
+    /// Creates messages moving `amount` to `destination`. Tax is deducted from everything
+    /// but native burns and delegations. Returns messages together with the amount of tax paid.
+    fn payout_msgs(
+        deps: DepsMut,
+        env: &Env,
+        destination: &Destination,
+        amount: Coin,
+    ) -> StdResult<(Vec<SubMsg>, Uint128)> {
+        let querier = &deps.querier;
+        match destination {
+            Destination::Address { address } => {
+                let (msg, tax) = send_with_tax(querier, address, amount)?;
+                Ok((vec![msg], tax))
+            }
+            Destination::NativeBurn {} => {
+                let msg = SubMsg::new(BankMsg::Burn {
+                    amount: vec![amount],
+                });
+                Ok((vec![msg], Uint128::zero()))
+            }
+            Destination::CommunityPool {} => {
+                let sent = deduct_tax(&amount, querier)?;
+                let tax = amount.amount - sent.amount;
+                let msg = SubMsg::new(fund_community_pool_msg(&env.contract.address, sent));
+                Ok((vec![msg], tax))
+            }
+            Destination::Contract { address, msg } => {
+                let sent = deduct_tax(&amount, querier)?;
+                let tax = amount.amount - sent.amount;
+                let msg = SubMsg::new(WasmMsg::Execute {
+                    contract_addr: address.to_string(),
+                    msg: msg.clone(),
+                    funds: vec![sent],
+                });
+                Ok((vec![msg], tax))
+            }
+            Destination::Stake { validators } => {
+                let mut msgs = vec![];
+                let mut left = amount.amount;
+                for (idx, (validator, weight)) in validators.iter().enumerate() {
+                    // last validator takes the rest, so no dust stays on the contract
+                    let part = if idx == validators.len() - 1 {
+                        left
+                    } else {
+                        amount.amount * *weight
+                    };
+                    left -= part;
+                    if part.is_zero() {
+                        continue;
+                    }
+                    msgs.push(SubMsg::new(StakingMsg::Delegate {
+                        validator: validator.clone(),
+                        amount: coin(part.u128(), amount.denom.clone()),
+                    }));
+                }
+                Ok((msgs, Uint128::zero()))
+            }
+            Destination::Ibc {
+                channel_id,
+                remote_address,
+                timeout_seconds,
+            } => {
+                let sent = deduct_tax(&amount, querier)?;
+                let tax = amount.amount - sent.amount;
+                let msg = ibc_transfer_msg(
+                    deps.storage,
+                    env,
+                    IbcTransfer {
+                        channel_id: channel_id.clone(),
+                        remote_address: remote_address.clone(),
+                        timeout_seconds: *timeout_seconds,
+                        amount: sent,
+                    },
+                )?;
+                Ok((vec![msg], tax))
+            }
+        }
+    }
+
+    /// Creates IBC transfer submessage and registers it as in flight, so it can be
+    /// escrowed by the reply handler if the transfer is rejected
+    fn ibc_transfer_msg(
+        storage: &mut dyn Storage,
+        env: &Env,
+        transfer: IbcTransfer,
+    ) -> StdResult<SubMsg> {
+        let msg = IbcMsg::Transfer {
+            channel_id: transfer.channel_id.clone(),
+            to_address: transfer.remote_address.clone(),
+            amount: transfer.amount.clone(),
+            timeout: IbcTimeout::with_timestamp(
+                env.block.time.plus_seconds(transfer.timeout_seconds),
+            ),
+        };
+        let mut in_flight = IBC_TRANSFERS_IN_FLIGHT
+            .may_load(storage)?
+            .unwrap_or_default();
+        in_flight.push(transfer);
+        IBC_TRANSFERS_IN_FLIGHT.save(storage, &in_flight)?;
+        Ok(SubMsg::reply_always(msg, IBC_TRANSFER_REPLY_ID))
+    }
+
+    /// Creates messages moving `amount` to the burn sinks, or to `burn_address` if there are none.
+    /// With buyback, `amount` is swapped to the target denom, which is burned by the reply handler.
+    /// Returns messages and total tax paid.
+    fn burn_msgs(
+        mut deps: DepsMut,
+        env: &Env,
+        config: &Config,
+        amount: Coin,
+    ) -> Result<(Vec<SubMsg>, Uint128), ContractError> {
+        if let BurnStrategy::BuybackAndBurn { pair, target_denom } = &config.burn_strategy {
+            if amount.amount.is_zero() {
+                return Ok((vec![], Uint128::zero()));
+            }
+            if amount.denom == *target_denom {
+                return Ok((
+                    vec![SubMsg::new(BankMsg::Burn {
+                        amount: vec![amount],
+                    })],
+                    Uint128::zero(),
+                ));
+            }
+            let offer = deduct_tax(&amount, &deps.querier)?;
+            let tax = amount.amount - offer.amount;
+            let msg = dex::swap_msg(&deps.querier, pair, offer, target_denom, config.max_spread)?;
+            let balance_before = deps
+                .querier
+                .query_balance(&env.contract.address, target_denom)?
+                .amount;
+            PENDING_BUYBACK.save(
+                deps.storage,
+                &PendingBuyback {
+                    target_denom: target_denom.clone(),
+                    balance_before,
+                },
+            )?;
+            return Ok((vec![SubMsg::reply_on_success(msg, BUYBACK_REPLY_ID)], tax));
+        }
+
+        if config.burn_sinks.is_empty() {
+            let (msg, tax) = send_with_tax(&deps.querier, &config.burn_address, amount)?;
+            return Ok((vec![msg], tax));
+        }
+
+        let mut msgs = vec![];
+        let mut tax_paid = Uint128::zero();
+        let mut left = amount.amount;
+        for (idx, WeightedBurnSink { sink, weight }) in config.burn_sinks.iter().enumerate() {
+            // last sink takes the rest, so no dust stays on the contract
+            let part = if idx == config.burn_sinks.len() - 1 {
+                left
+            } else {
+                amount.amount * *weight
+            };
+            left -= part;
+            if part.is_zero() {
+                continue;
+            }
+            let (sink_msgs, tax) = payout_msgs(
+                deps.branch(),
+                env,
+                sink,
+                coin(part.u128(), amount.denom.clone()),
+            )?;
+            tax_paid += tax;
+            msgs.extend(sink_msgs);
+        }
+        Ok((msgs, tax_paid))
+    }
+
     pub fn distribute(deps: DepsMut, env: Env, denom: String) -> Result<Response, ContractError> {
-        let contract_address = env.contract.address;
-        let balance: BalanceResponse =
-            deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
-                address: contract_address.to_string(),
-                denom: denom.clone(),
-            }))?;
-        let balance = balance.amount.amount;
-
-        if balance == Uint128::zero() {
-            return Err(ContractError::NoBalance {});
+        if let Some(pending) = PENDING_DISTRIBUTION.may_load(deps.storage)? {
+            return Err(ContractError::DistributionInProgress(pending.denom));
         }
+        let balance = query_available_balance(deps.as_ref(), &env, &denom)?;
 
         let config = CONFIG.load(deps.storage)?;
-        let amount_to_distribute = balance * config.percent_to_distribute;
-        let amount_to_burn = balance * config.percent_to_burn;
-        let amount_to_developer = balance * config.percent_to_developer;
-
-        let mut response = Response::new().add_submessages(vec![
-            SubMsg::new(BankMsg::Send {
-                to_address: config.burn_address.to_string(),
-                amount: vec![deduct_tax(
-                    &coin(amount_to_burn.u128(), denom.clone()),
-                    &deps.querier,
-                )?],
-            }),
-            SubMsg::new(BankMsg::Send {
-                to_address: config.developer_address.to_string(),
-                amount: vec![deduct_tax(
-                    &coin(amount_to_developer.u128(), denom.clone()),
-                    &deps.querier,
-                )?],
-            }),
-        ]);
+        let response = Response::new()
+            .add_attribute("action", "distribute")
+            .add_attribute("denom", denom.clone())
+            .add_attribute("balance", balance);
 
-        // Iter through whitelist
-        for wl_item in config.whitelist.iter() {
-            let weight_per_protocol = config
-                .weight_per_protocol
-                .iter()
-                // find appropriate protocol
-                .find(|wpp| wpp.protocol == wl_item.protocol);
-            // if contract has been found, add extra bank message
-            if let Some(wpp) = weight_per_protocol {
-                let amount = amount_to_distribute * wpp.weight;
-                let msg = SubMsg::new(BankMsg::Send {
-                    to_address: wl_item.address.to_string(),
-                    amount: vec![deduct_tax(
-                        &coin(amount.u128(), denom.clone()),
-                        &deps.querier,
-                    )?],
-                });
-                response = response.add_submessage(msg);
-            } else {
-                return Err(ContractError::MissingProtocol(wl_item.protocol.clone()));
+        if config.epoch_length == 0 {
+            if balance == Uint128::zero() {
+                return Err(ContractError::NoBalance {});
             }
+            assert_min_distribution(&deps.querier, &config, balance, &denom)?;
+            return swap_or_split(deps, &env, &config, denom, balance, response);
         }
 
-        Ok(response)
+        let height = env.block.height;
+        let mut epoch = match EPOCHS.may_load(deps.storage, &denom)? {
+            Some(epoch) if !epoch.is_finished() => epoch,
+            // Previous epoch has been fully paid out - snapshot balance for the next one
+            _ => {
+                if balance == Uint128::zero() {
+                    return Err(ContractError::NoBalance {});
+                }
+                let epoch = Epoch {
+                    start_height: height,
+                    end_height: height + config.epoch_length,
+                    amount: balance,
+                    released: Uint128::zero(),
+                };
+                EPOCHS.save(deps.storage, &denom, &epoch)?;
+                return Ok(response
+                    .add_attribute("epoch_start", epoch.start_height.to_string())
+                    .add_attribute("epoch_end", epoch.end_height.to_string())
+                    .add_attribute("epoch_amount", epoch.amount));
+            }
+        };
+
+        let releasable = epoch.releasable(height);
+        if releasable == Uint128::zero() {
+            return Err(ContractError::NothingUnlocked {});
+        }
+        assert_min_distribution(&deps.querier, &config, releasable, &denom)?;
+        epoch.released += releasable;
+        EPOCHS.save(deps.storage, &denom, &epoch)?;
+
+        let response = response.add_attribute("released", releasable);
+        swap_or_split(deps, &env, &config, denom, releasable, response)
     }
 
-    pub fn update_config(
+    /// Splits `amount` right away if it is already in the payout denom, otherwise swaps it
+    /// first and leaves the split to the reply handler
+    fn swap_or_split(
         deps: DepsMut,
-        info: MessageInfo,
-        admin: Option<String>,
-        burn_address: Option<String>,
-        developer_address: Option<String>,
-        whitelist: Option<Vec<Whitelist>>,
-        weight_per_protocol: Option<Vec<WeightPerProtocol>>,
+        env: &Env,
+        config: &Config,
+        denom: String,
+        amount: Uint128,
+        response: Response,
+    ) -> Result<Response, ContractError> {
+        let ask_denom = match &config.payout_denom {
+            Some(payout_denom) if *payout_denom != denom => payout_denom.clone(),
+            _ => return split(deps, env, config, denom, amount, response),
+        };
+
+        let offer = coin(amount.u128(), denom);
+        let msg = match config
+            .swap_pairs
+            .iter()
+            .find(|swap_pair| swap_pair.denom == offer.denom)
+        {
+            // funds sent to the pair are taxed, pair expects exactly what it receives
+            Some(swap_pair) => dex::swap_msg(
+                &deps.querier,
+                &swap_pair.pair,
+                deduct_tax(&offer, &deps.querier)?,
+                &ask_denom,
+                config.max_spread,
+            )?,
+            None => create_swap_msg(offer.clone(), ask_denom.clone()),
+        };
+        let balance_before = query_available_balance(deps.as_ref(), env, &ask_denom)?;
+        PENDING_SWAP.save(
+            deps.storage,
+            &PendingSwap {
+                offer: offer.clone(),
+                ask_denom: ask_denom.clone(),
+                balance_before,
+            },
+        )?;
+
+        Ok(response
+            .add_attribute("swapped", offer.to_string())
+            .add_attribute("payout_denom", ask_denom)
+            .add_submessage(SubMsg::reply_on_success(msg, SWAP_REPLY_ID)))
+    }
+
+    /// Converts `amount` of `base_denom` to `denom` with the oracle exchange rate
+    fn convert_threshold(
+        querier: &QuerierWrapper,
+        base_denom: &str,
+        amount: Uint128,
+        denom: &str,
+    ) -> Result<Uint128, ContractError> {
+        if amount.is_zero() || base_denom == denom {
+            return Ok(amount);
+        }
+        let rates = TerraQuerier::new(querier).query_exchange_rates(base_denom, vec![denom])?;
+        rates
+            .exchange_rates
+            .into_iter()
+            .find(|rate| rate.quote_denom == denom)
+            .map(|rate| amount * rate.exchange_rate)
+            .ok_or_else(|| ContractError::NoExchangeRate(base_denom.to_owned(), denom.to_owned()))
+    }
+
+    /// Makes sure `amount` of `denom` is worth at least `min_distribution`
+    fn assert_min_distribution(
+        querier: &QuerierWrapper,
+        config: &Config,
+        amount: Uint128,
+        denom: &str,
+    ) -> Result<(), ContractError> {
+        let min = convert_threshold(
+            querier,
+            &config.threshold_denom,
+            config.min_distribution,
+            denom,
+        )?;
+        if amount < min {
+            return Err(ContractError::BelowMinDistribution(
+                amount.u128(),
+                min.u128(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Splits `amount` between burn address, developer and whitelisted protocols
+    pub fn split(
+        mut deps: DepsMut,
+        env: &Env,
+        config: &Config,
+        denom: String,
+        amount: Uint128,
+        response: Response,
+    ) -> Result<Response, ContractError> {
+        let amount_to_distribute = amount * config.percent_to_distribute;
+        let amount_to_developer = amount * config.percent_to_developer;
+
+        let (recipients, mut protocol_shares) = match &config.whitelist_group {
+            Some(group) => group_shares(deps.branch(), env, group, amount_to_distribute)?,
+            None => whitelist_shares(deps.storage, amount_to_distribute)?,
+        };
+        let overflow = apply_caps(
+            deps.storage,
+            env,
+            config,
+            &denom,
+            &recipients,
+            &mut protocol_shares,
+        )?;
+        let amount_to_burn = amount * config.percent_to_burn + overflow;
+
+        let (burn_msgs, burn_tax) = burn_msgs(
+            deps.branch(),
+            env,
+            config,
+            coin(amount_to_burn.u128(), denom.clone()),
+        )?;
+        let mut tax_paid = burn_tax;
+        let mut response = response
+            .add_attribute("burned", amount_to_burn - burn_tax)
+            .add_submessages(burn_msgs);
+        if !overflow.is_zero() {
+            response = response.add_attribute("cap_overflow_burned", overflow);
+        }
+
+        if config.developer_vesting.is_some() {
+            // keep developer share on the contract until it vests
+            let mut vesting = DEVELOPER_VESTING
+                .may_load(deps.storage, &denom)?
+                .unwrap_or_default();
+            vesting.accrued += amount_to_developer;
+            DEVELOPER_VESTING.save(deps.storage, &denom, &vesting)?;
+            response = response.add_attribute("to_developer_vesting", amount_to_developer);
+        } else {
+            let (developer_msgs, developer_tax) = payout_msgs(
+                deps.branch(),
+                env,
+                &config.developer_payout(),
+                coin(amount_to_developer.u128(), denom.clone()),
+            )?;
+            tax_paid += developer_tax;
+            response = response
+                .add_attribute("to_developer", amount_to_developer - developer_tax)
+                .add_submessages(developer_msgs);
+        }
+
+        if config.distribution_batch_size > 0 {
+            let queued = queue_payouts(deps.storage, &denom, &recipients, protocol_shares)?;
+            return Ok(response
+                .add_attribute("queued_payouts", queued.to_string())
+                .add_attribute("tax_paid", tax_paid));
+        }
+
+        for (wl_item, amount) in recipients.iter().zip(protocol_shares) {
+            // recipient may have its cap used up already
+            if amount.is_zero() {
+                continue;
+            }
+            let (msgs, tax) = payout_msgs(
+                deps.branch(),
+                env,
+                &wl_item.payout(),
+                coin(amount.u128(), denom.clone()),
+            )?;
+            tax_paid += tax;
+            response = response
+                .add_event(payout_event(
+                    &wl_item.protocol,
+                    &wl_item.address,
+                    amount - tax,
+                ))
+                .add_submessages(msgs);
+        }
+
+        Ok(response.add_attribute("tax_paid", tax_paid))
+    }
+
+    /// One event per protocol payout, as several addresses can share a protocol
+    fn payout_event(protocol: &str, address: &Addr, amount: Uint128) -> Event {
+        Event::new("payout")
+            .add_attribute("protocol", protocol)
+            .add_attribute("address", address)
+            .add_attribute("amount", amount)
+    }
+
+    /// Snapshots non-zero protocol payouts to be paid out by `ContinueDistribution`.
+    /// Returns number of queued payouts.
+    fn queue_payouts(
+        storage: &mut dyn Storage,
+        denom: &str,
+        recipients: &[Whitelist],
+        shares: Vec<Uint128>,
+    ) -> StdResult<u64> {
+        let mut total = 0u64;
+        let mut remaining = Uint128::zero();
+        for (wl_item, amount) in recipients.iter().zip(shares) {
+            if amount.is_zero() {
+                continue;
+            }
+            let payout = QueuedPayout {
+                protocol: wl_item.protocol.clone(),
+                address: wl_item.address.clone(),
+                destination: wl_item.payout(),
+                amount,
+            };
+            QUEUED_PAYOUTS.save(storage, total.into(), &payout)?;
+            total += 1;
+            remaining += amount;
+        }
+        if total > 0 {
+            PENDING_DISTRIBUTION.save(
+                storage,
+                &PendingDistribution {
+                    denom: denom.to_owned(),
+                    total,
+                    next: 0,
+                    remaining,
+                },
+            )?;
+        }
+        Ok(total)
+    }
+
+    pub fn continue_distribution(
+        mut deps: DepsMut,
+        env: Env,
+        limit: Option<u32>,
     ) -> Result<Response, ContractError> {
-        let mut config = CONFIG.load(deps.storage)?;
+        let config = CONFIG.load(deps.storage)?;
+        let mut pending = PENDING_DISTRIBUTION
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoDistributionInProgress {})?;
+
+        // zero limit, e.g. when batching has been turned off meanwhile, pays out everything
+        let limit = limit.unwrap_or(config.distribution_batch_size) as u64;
+        let end = if limit == 0 {
+            pending.total
+        } else {
+            pending.total.min(pending.next + limit)
+        };
+
+        let mut response = Response::new()
+            .add_attribute("action", "continue_distribution")
+            .add_attribute("denom", pending.denom.clone());
+        let mut tax_paid = Uint128::zero();
+        for id in pending.next..end {
+            let payout = QUEUED_PAYOUTS.load(deps.storage, id.into())?;
+            QUEUED_PAYOUTS.remove(deps.storage, id.into());
+            let (msgs, tax) = payout_msgs(
+                deps.branch(),
+                &env,
+                &payout.destination,
+                coin(payout.amount.u128(), pending.denom.clone()),
+            )?;
+            tax_paid += tax;
+            pending.remaining -= payout.amount;
+            response = response
+                .add_event(payout_event(
+                    &payout.protocol,
+                    &payout.address,
+                    payout.amount - tax,
+                ))
+                .add_submessages(msgs);
+        }
+        pending.next = end;
+
+        if pending.next == pending.total {
+            PENDING_DISTRIBUTION.remove(deps.storage);
+        } else {
+            PENDING_DISTRIBUTION.save(deps.storage, &pending)?;
+        }
+
+        Ok(response
+            .add_attribute(
+                "remaining_payouts",
+                (pending.total - pending.next).to_string(),
+            )
+            .add_attribute("tax_paid", tax_paid))
+    }
+
+    /// Returns whitelist entries with their part of `amount`
+    fn whitelist_shares(
+        storage: &dyn Storage,
+        amount: Uint128,
+    ) -> Result<(Vec<Whitelist>, Vec<Uint128>), ContractError> {
+        let whitelist = load_whitelist(storage)?;
+        let mut shares = Vec::with_capacity(whitelist.len());
+        for wl_item in whitelist.iter() {
+            let weight = WEIGHTS
+                .may_load(storage, &wl_item.protocol)?
+                .ok_or_else(|| ContractError::MissingProtocol(wl_item.protocol.clone()))?;
+            shares.push(amount * weight);
+        }
+        Ok((whitelist, shares))
+    }
+
+    /// Returns group members as whitelist entries named after their addresses,
+    /// with `amount` split proportionally to member weights
+    fn group_shares(
+        deps: DepsMut,
+        env: &Env,
+        group: &Addr,
+        amount: Uint128,
+    ) -> StdResult<(Vec<Whitelist>, Vec<Uint128>)> {
+        let snapshot = match GROUP_SNAPSHOT.may_load(deps.storage)? {
+            Some(snapshot) if snapshot.group == *group => snapshot,
+            _ => load_group_snapshot(deps.storage, &deps.querier, env, group)?,
+        };
+        let mut recipients = vec![];
+        let mut shares = vec![];
+        if snapshot.total_weight == 0 {
+            return Ok((recipients, shares));
+        }
+        for item in GROUP_MEMBERS.range(deps.storage, None, None, Order::Ascending) {
+            let (address, weight) = item?;
+            let address = Addr::unchecked(String::from_utf8(address).map_err(StdError::from)?);
+            recipients.push(Whitelist {
+                protocol: address.to_string(),
+                address,
+                destination: None,
+            });
+            shares.push(amount.multiply_ratio(weight, snapshot.total_weight));
+        }
+        Ok((recipients, shares))
+    }
+
+    /// Lowers protocol shares to what recipient caps allow and records cap usage.
+    /// Depending on `cap_overflow`, the part above caps is split between uncapped
+    /// whitelist entries or returned to be burned.
+    fn apply_caps(
+        storage: &mut dyn Storage,
+        env: &Env,
+        config: &Config,
+        denom: &str,
+        recipients: &[Whitelist],
+        shares: &mut [Uint128],
+    ) -> StdResult<Uint128> {
+        let mut caps = vec![];
+        for cap in config
+            .recipient_caps
+            .iter()
+            .filter(|cap| cap.denom == denom)
+        {
+            let usage = load_cap_usage(storage, env, config, cap)?;
+            let received = usage.received();
+            // paid out by this distribution so far
+            caps.push((cap, usage, received, Uint128::zero()));
+        }
+        if caps.is_empty() {
+            return Ok(Uint128::zero());
+        }
+
+        let mut overflow = Uint128::zero();
+        let mut uncapped = vec![];
+        for (idx, wl_item) in recipients.iter().enumerate() {
+            let mut allowed = shares[idx];
+            let mut capped = false;
+            for (cap, _, received, paid) in caps.iter() {
+                if !cap.target.applies_to(wl_item) {
+                    continue;
+                }
+                capped = true;
+                if let Some(limit) = cap.per_distribution {
+                    allowed = allowed.min(limit.saturating_sub(*paid));
+                }
+                if let Some(limit) = cap.per_window {
+                    allowed = allowed.min(limit.saturating_sub(*received));
+                }
+            }
+            if !capped {
+                uncapped.push(idx);
+                continue;
+            }
+            for (cap, _, received, paid) in caps.iter_mut() {
+                if cap.target.applies_to(wl_item) {
+                    *paid += allowed;
+                    *received += allowed;
+                }
+            }
+            overflow += shares[idx] - allowed;
+            shares[idx] = allowed;
+        }
+
+        for (cap, mut usage, _, paid) in caps {
+            if cap.per_window.is_some() {
+                if !paid.is_zero() {
+                    usage.payouts.push(CapPayout {
+                        time: env.block.time,
+                        amount: paid,
+                    });
+                }
+                CAP_USAGE.save(storage, (&cap.target.key(), denom), &usage)?;
+            }
+        }
+
+        let uncapped_total = uncapped
+            .iter()
+            .fold(Uint128::zero(), |total, idx| total + shares[*idx]);
+        if config.cap_overflow == CapOverflow::Burn || uncapped_total.is_zero() {
+            return Ok(overflow);
+        }
+
+        let mut left = overflow;
+        for (n, idx) in uncapped.iter().enumerate() {
+            // last uncapped entry takes rounding remainder
+            let extra = if n == uncapped.len() - 1 {
+                left
+            } else {
+                overflow.multiply_ratio(shares[*idx], uncapped_total)
+            };
+            left -= extra;
+            shares[*idx] += extra;
+        }
+        Ok(Uint128::zero())
+    }
+
+    /// Makes sure that config is still updatable and that sender is its admin
+    fn assert_admin(api: &dyn Api, config: &Config, sender: &Addr) -> Result<(), ContractError> {
         if config.admin.is_empty() {
             return Err(ContractError::ConfigNotUpdatable {});
         }
-        let cfg_admin = deps.api.addr_validate(&config.admin)?;
-        if cfg_admin != info.sender {
+        let cfg_admin = api.addr_validate(&config.admin)?;
+        if cfg_admin != *sender {
             return Err(ContractError::Unauthorized {});
         }
+        Ok(())
+    }
+
+    /// Validates and stores whitelist and weights set in `change`, which live apart from
+    /// `Config`. Returns changed entries to be recorded in config history.
+    pub fn apply_recipients_change(
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        change: &ConfigChange,
+    ) -> Result<Vec<FieldChange>, ContractError> {
+        let mut changes = vec![];
+        if let Some(whitelist) = &change.whitelist {
+            let whitelist = validate_whitelist(api, whitelist.clone())?;
+            changes.extend(replace_whitelist(storage, whitelist)?);
+        }
+        if let Some(weights) = &change.weight_per_protocol {
+            changes.extend(replace_weights(storage, convert_weights(weights.clone()))?);
+        }
+        if let Some(approval) = &change.approve_application {
+            changes.extend(approve(storage, approval.id, approval.weight)?);
+        }
+        Ok(changes)
+    }
 
-        if let Some(admin) = admin {
+    /// Validates and applies all set fields of `change` to `config`.
+    /// Whitelist and weights are handled by `apply_recipients_change`.
+    pub fn apply_config_change(
+        api: &dyn Api,
+        config: &mut Config,
+        change: ConfigChange,
+    ) -> Result<(), ContractError> {
+        if let Some(admin) = change.admin {
             config.admin = admin
         }
 
-        if let Some(burn_address) = burn_address {
-            config.burn_address = deps.api.addr_validate(&burn_address)?;
+        if let Some(burn_address) = change.burn_address {
+            config.burn_address = api.addr_validate(&burn_address)?;
+        }
+
+        if let Some(developer_address) = change.developer_address {
+            config.developer_address = api.addr_validate(&developer_address)?;
+        }
+
+        if let Some(config_change_delay) = change.config_change_delay {
+            config.config_change_delay = config_change_delay;
+        }
+
+        if let Some(epoch_length) = change.epoch_length {
+            config.epoch_length = epoch_length;
+        }
+
+        match (change.developer_vesting, change.disable_developer_vesting) {
+            (Some(_), Some(true)) => return Err(ContractError::ConflictingDeveloperVesting {}),
+            (_, Some(true)) => config.developer_vesting = None,
+            (Some(developer_vesting), _) => config.developer_vesting = Some(developer_vesting),
+            (None, _) => {}
         }
 
-        if let Some(developer_address) = developer_address {
-            config.developer_address = deps.api.addr_validate(&developer_address)?;
+        if let Some(developer_destination) = change.developer_destination {
+            config.developer_destination = Some(validate_destination(api, developer_destination)?);
         }
 
-        if let Some(whitelist) = whitelist {
-            config.whitelist.clear();
-            for entry in whitelist {
-                let address = deps.api.addr_validate(&entry.address)?;
-                config.whitelist.push(crate::state::Whitelist {
-                    address,
-                    protocol: entry.protocol.clone(),
+        if let Some(burn_sinks) = change.burn_sinks {
+            let total_weight = burn_sinks
+                .iter()
+                .fold(Decimal::zero(), |total, sink| total + sink.weight);
+            if !burn_sinks.is_empty() && total_weight != Decimal::one() {
+                return Err(ContractError::InvalidBurnSinkWeights {});
+            }
+            config.burn_sinks.clear();
+            for entry in burn_sinks {
+                config.burn_sinks.push(WeightedBurnSink {
+                    sink: validate_destination(api, entry.sink)?,
+                    weight: entry.weight,
                 });
             }
         }
 
-        if let Some(weight_per_protocol) = weight_per_protocol {
-            config.weight_per_protocol.clear();
-            for entry in weight_per_protocol {
-                config
-                    .weight_per_protocol
-                    .push(crate::state::WeightPerProtocol {
-                        protocol: entry.protocol.clone(),
-                        weight: entry.weight,
-                    });
+        if let Some(recipient_caps) = change.recipient_caps {
+            config.recipient_caps.clear();
+            for cap in recipient_caps {
+                let target = match cap.target {
+                    crate::msg::CapTarget::Protocol { protocol } => {
+                        CapTarget::Protocol { protocol }
+                    }
+                    crate::msg::CapTarget::Address { address } => CapTarget::Address {
+                        address: api.addr_validate(&address)?,
+                    },
+                };
+                config.recipient_caps.push(RecipientCap {
+                    target,
+                    denom: cap.denom,
+                    per_distribution: cap.per_distribution,
+                    per_window: cap.per_window,
+                });
+            }
+        }
+
+        if let Some(cap_window) = change.cap_window {
+            config.cap_window = cap_window;
+        }
+
+        if let Some(cap_overflow) = change.cap_overflow {
+            config.cap_overflow = cap_overflow;
+        }
+
+        if let Some(requires_accept) = change.address_change_requires_accept {
+            config.address_change_requires_accept = requires_accept;
+        }
+
+        if let Some(threshold) = change.less_then_threshold {
+            config.less_then_threshold = threshold;
+        }
+
+        if let Some(threshold_denom) = change.threshold_denom {
+            config.threshold_denom = threshold_denom;
+        }
+
+        if let Some(min_distribution) = change.min_distribution {
+            config.min_distribution = min_distribution;
+        }
+
+        if let Some(deposit_denoms) = change.deposit_denoms {
+            config.deposit_denoms = deposit_denoms;
+        }
+
+        if let Some(batch_size) = change.distribution_batch_size {
+            config.distribution_batch_size = batch_size;
+        }
+
+        if let Some(payout_denom) = change.payout_denom {
+            config.payout_denom = if payout_denom.is_empty() {
+                None
+            } else {
+                Some(payout_denom)
+            };
+        }
+
+        if let Some(swap_pairs) = change.swap_pairs {
+            config.swap_pairs = swap_pairs
+                .into_iter()
+                .map(|entry| {
+                    Ok(SwapPair {
+                        denom: entry.denom,
+                        pair: api.addr_validate(&entry.pair)?,
+                    })
+                })
+                .collect::<StdResult<_>>()?;
+        }
+
+        if let Some(max_spread) = change.max_spread {
+            if max_spread > Decimal::one() {
+                return Err(ContractError::InvalidMaxSpread {});
+            }
+            config.max_spread = max_spread;
+        }
+
+        if let Some(burn_strategy) = change.burn_strategy {
+            config.burn_strategy = match burn_strategy {
+                crate::msg::BurnStrategy::Direct {} => BurnStrategy::Direct {},
+                crate::msg::BurnStrategy::BuybackAndBurn { pair, target_denom } => {
+                    BurnStrategy::BuybackAndBurn {
+                        pair: api.addr_validate(&pair)?,
+                        target_denom,
+                    }
+                }
+            };
+        }
+
+        if let Some(group) = change.whitelist_group {
+            config.whitelist_group = if group.is_empty() {
+                None
+            } else {
+                Some(api.addr_validate(&group)?)
+            };
+        }
+
+        if let Some(weight_voting) = change.weight_voting {
+            let voters = match weight_voting.voters {
+                crate::msg::VoterSet::Fixed { voters } => {
+                    if voters.is_empty() || voters.iter().any(|(_, weight)| *weight == 0) {
+                        return Err(ContractError::InvalidWeightVoting {});
+                    }
+                    VoterSet::Fixed {
+                        voters: voters
+                            .into_iter()
+                            .map(|(address, weight)| {
+                                Ok(Voter {
+                                    address: api.addr_validate(&address)?,
+                                    weight,
+                                })
+                            })
+                            .collect::<StdResult<_>>()?,
+                    }
+                }
+                crate::msg::VoterSet::Group { contract } => VoterSet::Group {
+                    contract: api.addr_validate(&contract)?,
+                },
+            };
+            if weight_voting.voting_period == 0
+                || weight_voting.threshold.is_zero()
+                || weight_voting.threshold > Decimal::one()
+            {
+                return Err(ContractError::InvalidWeightVoting {});
             }
+            config.weight_voting = Some(WeightVoting {
+                voters,
+                voting_period: weight_voting.voting_period,
+                threshold: weight_voting.threshold,
+            });
         }
 
-        CONFIG.save(deps.storage, &config)?;
+        if config.cap_window == 0
+            && config
+                .recipient_caps
+                .iter()
+                .any(|cap| cap.per_window.is_some())
+        {
+            return Err(ContractError::MissingCapWindow {});
+        }
 
-        Ok(Response::new())
+        Ok(())
     }
 
-    pub fn burn_the_bottom(
+    /// Saves new config and appends a revision to config history if anything changed,
+    /// including already stored `recipient_changes` of whitelist and weights.
+    /// Returns `update_config` event describing changed fields.
+    pub fn save_config(
         deps: DepsMut,
-        env: Env,
-        denom: String,
-    ) -> Result<Response, ContractError> {
-        let contract_address = env.contract.address;
-        let balance: BalanceResponse =
-            deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
-                address: contract_address.to_string(),
-                denom,
-            }))?;
-        let balance_amount = balance.amount.amount;
+        env: &Env,
+        sender: &Addr,
+        action: &str,
+        before: &Config,
+        after: &Config,
+        recipient_changes: Vec<FieldChange>,
+    ) -> Result<Option<Event>, ContractError> {
+        CONFIG.save(deps.storage, after)?;
 
-        // If balance left on contract if bigger then provided "less_then" value,
-        // do nothing.
-        let config = CONFIG.load(deps.storage)?;
-        if balance_amount > config.less_then_threshold {
-            return Err(ContractError::BurnTheBottom(
-                balance_amount.u128(),
-                config.less_then_threshold.u128(),
-            ));
+        let mut changes = before.diff(after)?;
+        changes.extend(recipient_changes);
+        if changes.is_empty() {
+            return Ok(None);
         }
-        // otherwise, burn the leftover tokens
-        Ok(Response::new().add_submessage(SubMsg::new(BankMsg::Send {
-            to_address: config.burn_address.to_string(),
-            amount: vec![deduct_tax(&balance.amount, &deps.querier)?],
-        })))
+
+        let event = Event::new("update_config").add_attributes(
+            changes
+                .iter()
+                .map(|change| (change.field.clone(), change.after.clone())),
+        );
+        let id = record_revision(deps.storage, env, sender, action, changes)?;
+
+        Ok(Some(
+            event
+                .add_attribute("revision", id.to_string())
+                .add_attribute("sender", sender.to_string())
+                .add_attribute("action", action),
+        ))
     }
-}
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_binary(&query::config(deps)?),
+    /// Appends a revision to config history. Returns its id.
+    fn record_revision(
+        storage: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        action: &str,
+        changes: Vec<FieldChange>,
+    ) -> StdResult<u64> {
+        let id = CONFIG_REVISION_COUNT.may_load(storage)?.unwrap_or_default();
+        CONFIG_REVISION_COUNT.save(storage, &(id + 1))?;
+        CONFIG_HISTORY.save(
+            storage,
+            U64Key::new(id),
+            &ConfigRevision {
+                id,
+                sender: sender.clone(),
+                action: action.to_owned(),
+                height: env.block.height,
+                time: env.block.time,
+                changes,
+            },
+        )?;
+        Ok(id)
     }
-}
 
-mod query {
-    use super::*;
+    /// Sends `amount` of `denom`, or whole contract balance of it, to `recipient`.
+    /// Sweep is recorded in config history as a change of the contract balance.
+    pub fn sweep(
+        deps: DepsMut,
+        env: &Env,
+        sender: &Addr,
+        action: &str,
+        denom: String,
+        amount: Option<Uint128>,
+        recipient: String,
+    ) -> Result<Response, ContractError> {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &denom)?
+            .amount;
+        // tokens reserved for vesting, escrowed transfers and queued payouts cannot be swept
+        let available = query_available_balance(deps.as_ref(), env, &denom)?;
+        let amount = amount.unwrap_or(available);
+        if amount.is_zero() {
+            return Err(ContractError::NoBalance {});
+        }
+        if amount > available {
+            return Err(ContractError::SweepExceedsAvailable(
+                amount.u128(),
+                available.u128(),
+            ));
+        }
 
-    pub fn config(deps: Deps) -> StdResult<Config> {
-        CONFIG.load(deps.storage)
+        let (msg, tax) = send_with_tax(
+            &deps.querier,
+            &recipient,
+            coin(amount.u128(), denom.clone()),
+        )?;
+        let id = record_revision(
+            deps.storage,
+            env,
+            sender,
+            action,
+            vec![
+                FieldChange::new(
+                    &format!("balance_{}", denom),
+                    &balance,
+                    &balance.saturating_sub(amount),
+                )?,
+                FieldChange::new("sweep_recipient", &None, &Some(recipient.clone()))?,
+            ],
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", action)
+            .add_attribute("revision", id.to_string())
+            .add_attribute("recipient", recipient)
+            .add_attribute("amount", amount - tax)
+            .add_attribute("tax_paid", tax)
+            .add_submessage(msg))
     }
-}
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+    /// Sweeping is allowed only while paused, so it cannot race with payouts.
+    /// With timelock enabled, only governance can sweep.
+    pub fn admin_sweep(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        denom: String,
+        amount: Option<Uint128>,
+        recipient: String,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        if config.config_change_delay > 0 {
+            return Err(ContractError::TimelockActive {});
+        }
+        if !is_paused(deps.storage)? {
+            return Err(ContractError::NotPaused {});
+        }
+        sweep(deps, &env, &info.sender, "sweep", denom, amount, recipient)
+    }
+
+    pub fn deposit(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        let funds = match info.funds.as_slice() {
+            [] => return Err(ContractError::NoFunds {}),
+            [funds] => funds,
+            _ => return Err(ContractError::DepositMoreThenOne {}),
+        };
+
+        let config = CONFIG.load(deps.storage)?;
+        if !config.deposit_denoms.is_empty() && !config.deposit_denoms.contains(&funds.denom) {
+            return Err(ContractError::UnsupportedDenom(funds.denom.clone()));
+        }
+
+        let assigned_to_burn = funds.amount * config.percent_to_burn;
+        contributions().update(
+            deps.storage,
+            (&info.sender, &funds.denom),
+            |contribution| -> StdResult<_> {
+                let mut contribution = contribution.unwrap_or_else(|| Contribution {
+                    depositor: info.sender.clone(),
+                    denom: funds.denom.clone(),
+                    deposited: Uint128::zero(),
+                    assigned_to_burn: Uint128::zero(),
+                });
+                contribution.deposited += funds.amount;
+                contribution.assigned_to_burn += assigned_to_burn;
+                Ok(contribution)
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "deposit")
+            .add_attribute("depositor", info.sender)
+            .add_attribute("amount", funds.to_string()))
+    }
+
+    pub fn set_paused(
+        deps: DepsMut,
+        info: MessageInfo,
+        paused: bool,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        PAUSED.save(deps.storage, &paused)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_paused")
+            .add_attribute("paused", paused.to_string()))
+    }
+
+    pub fn update_config(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        change: ConfigChange,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        // With timelock enabled, changes have to go through ProposeConfigChange
+        if config.config_change_delay > 0 {
+            return Err(ContractError::TimelockActive {});
+        }
+
+        let recipient_changes = apply_recipients_change(deps.storage, deps.api, &change)?;
+        let mut new_config = config.clone();
+        apply_config_change(deps.api, &mut new_config, change)?;
+        let event = save_config(
+            deps,
+            &env,
+            &info.sender,
+            "update_config",
+            &config,
+            &new_config,
+            recipient_changes,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_config")
+            .add_events(event))
+    }
+
+    pub fn propose_config_change(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        change: ConfigChange,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        if PENDING_CONFIG_CHANGE.may_load(deps.storage)?.is_some() {
+            return Err(ContractError::ConfigChangeAlreadyPending {});
+        }
+
+        // Validate change upfront, so invalid proposal won't block the queue
+        apply_config_change(deps.api, &mut config.clone(), change.clone())?;
+        if let Some(whitelist) = &change.whitelist {
+            validate_whitelist(deps.api, whitelist.clone())?;
+        }
+        if let Some(approval) = &change.approve_application {
+            load_pending_application(deps.storage, approval.id)?;
+        }
+
+        let executable_after = env.block.time.plus_seconds(config.config_change_delay);
+        PENDING_CONFIG_CHANGE.save(
+            deps.storage,
+            &PendingConfigChange {
+                change,
+                proposed_at: env.block.time,
+                executable_after,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "propose_config_change")
+            .add_attribute("executable_after", executable_after.to_string()))
+    }
+
+    pub fn execute_config_change(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        let pending = PENDING_CONFIG_CHANGE
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoPendingConfigChange {})?;
+        if env.block.time < pending.executable_after {
+            return Err(ContractError::ConfigChangeNotExecutable(
+                pending.executable_after,
+            ));
+        }
+
+        let recipient_changes = apply_recipients_change(deps.storage, deps.api, &pending.change)?;
+        let mut new_config = config.clone();
+        apply_config_change(deps.api, &mut new_config, pending.change)?;
+        PENDING_CONFIG_CHANGE.remove(deps.storage);
+        let event = save_config(
+            deps,
+            &env,
+            &info.sender,
+            "execute_config_change",
+            &config,
+            &new_config,
+            recipient_changes,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "execute_config_change")
+            .add_events(event))
+    }
+
+    pub fn cancel_config_change(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        if PENDING_CONFIG_CHANGE.may_load(deps.storage)?.is_none() {
+            return Err(ContractError::NoPendingConfigChange {});
+        }
+        PENDING_CONFIG_CHANGE.remove(deps.storage);
+
+        Ok(Response::new().add_attribute("action", "cancel_config_change"))
+    }
+
+    pub fn burn_the_bottom(
+        deps: DepsMut,
+        env: Env,
+        denom: String,
+    ) -> Result<Response, ContractError> {
+        // tokens locked in the open epoch are burned as well
+        let balance_amount = query_available_balance(deps.as_ref(), &env, &denom)?
+            + locked_in_epoch(deps.storage, &denom)?;
+
+        // If balance left on contract if bigger then provided "less_then" value,
+        // do nothing.
+        let config = CONFIG.load(deps.storage)?;
+        // denoms the oracle doesn't quote (e.g. IBC tokens) are compared with the raw threshold,
+        // so their leftovers can still be burned
+        let threshold = convert_threshold(
+            &deps.querier,
+            &config.threshold_denom,
+            config.less_then_threshold,
+            &denom,
+        )
+        .unwrap_or(config.less_then_threshold);
+        if balance_amount > threshold {
+            return Err(ContractError::BurnTheBottom(
+                balance_amount.u128(),
+                threshold.u128(),
+            ));
+        }
+        // otherwise, burn the leftover tokens, including ones locked in current epoch
+        EPOCHS.remove(deps.storage, &denom);
+        let (msgs, tax) = burn_msgs(
+            deps,
+            &env,
+            &config,
+            coin(balance_amount.u128(), denom.clone()),
+        )?;
+        Ok(Response::new()
+            .add_attribute("action", "burn_the_bottom")
+            .add_attribute("denom", denom)
+            .add_attribute("balance", balance_amount)
+            .add_attribute("burned", balance_amount - tax)
+            .add_attribute("tax_paid", tax)
+            .add_submessages(msgs))
+    }
+
+    pub fn claim_developer_vested(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if info.sender != config.developer_address {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let balances = DEVELOPER_VESTING
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut response = Response::new().add_attribute("action", "claim_developer_vested");
+        for (denom, mut vesting) in balances {
+            let denom = String::from_utf8(denom).map_err(StdError::from)?;
+            let vested = match &config.developer_vesting {
+                Some(schedule) => schedule.vested(vesting.accrued, env.block.time),
+                None => vesting.accrued,
+            };
+            // schedule may have been replaced by a later one since the last claim
+            let claimable = vested.saturating_sub(vesting.claimed);
+            if claimable.is_zero() {
+                continue;
+            }
+            vesting.claimed += claimable;
+            DEVELOPER_VESTING.save(deps.storage, &denom, &vesting)?;
+
+            let (msgs, tax) = payout_msgs(
+                deps.branch(),
+                &env,
+                &config.developer_payout(),
+                coin(claimable.u128(), denom.clone()),
+            )?;
+            response = response
+                .add_attribute(format!("claimed_{}", denom), claimable - tax)
+                .add_submessages(msgs);
+        }
+
+        if response.messages.is_empty() {
+            return Err(ContractError::NothingToClaim {});
+        }
+        Ok(response)
+    }
+
+    pub fn claim_staking_rewards(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let delegations = deps
+            .querier
+            .query_all_delegations(env.contract.address.to_string())?;
+        if delegations.is_empty() {
+            return Err(ContractError::NoDelegations {});
+        }
+
+        let msgs = delegations.into_iter().map(|delegation| {
+            SubMsg::new(DistributionMsg::WithdrawDelegatorReward {
+                validator: delegation.validator,
+            })
+        });
+        Ok(Response::new()
+            .add_attribute("action", "claim_staking_rewards")
+            .add_submessages(msgs))
+    }
+
+    pub fn apply_for_whitelist(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        protocol: String,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        let address = deps.api.addr_validate(&address)?;
+        if let Some(entry) = whitelist().may_load(deps.storage, &address)? {
+            return Err(ContractError::AlreadyWhitelisted(
+                address.to_string(),
+                entry.protocol,
+            ));
+        }
+
+        let id = APPLICATION_COUNT
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        APPLICATION_COUNT.save(deps.storage, &(id + 1))?;
+        APPLICATIONS.save(
+            deps.storage,
+            U64Key::new(id),
+            &Application {
+                id,
+                protocol: protocol.clone(),
+                address: address.clone(),
+                applicant: info.sender.clone(),
+                submitted_at: env.block.time,
+                status: ApplicationStatus::Pending,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "apply_for_whitelist")
+            .add_attribute("id", id.to_string())
+            .add_attribute("protocol", protocol)
+            .add_attribute("address", address)
+            .add_attribute("applicant", info.sender))
+    }
+
+    /// Loads application which still waits for a decision
+    fn load_pending_application(
+        storage: &dyn Storage,
+        id: u64,
+    ) -> Result<Application, ContractError> {
+        let application = APPLICATIONS
+            .may_load(storage, U64Key::new(id))?
+            .ok_or(ContractError::NoSuchApplication(id))?;
+        if application.status != ApplicationStatus::Pending {
+            return Err(ContractError::ApplicationNotPending(id));
+        }
+        Ok(application)
+    }
+
+    pub fn approve_application(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+        weight: Option<Decimal>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        // Approval changes config, so it is subject to the same timelock as other changes
+        if config.config_change_delay > 0 {
+            return propose_config_change(
+                deps,
+                env,
+                info,
+                ConfigChange {
+                    approve_application: Some(ApplicationApproval { id, weight }),
+                    ..ConfigChange::default()
+                },
+            );
+        }
+
+        let recipient_changes = approve(deps.storage, id, weight)?;
+        let event = save_config(
+            deps,
+            &env,
+            &info.sender,
+            "approve_application",
+            &config,
+            &config,
+            recipient_changes,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "approve_application")
+            .add_attribute("id", id.to_string())
+            .add_events(event))
+    }
+
+    /// Whitelists address of pending application `id`, setting weight of its protocol if given.
+    /// Returns changed entries to be recorded in config history.
+    fn approve(
+        storage: &mut dyn Storage,
+        id: u64,
+        weight: Option<Decimal>,
+    ) -> Result<Vec<FieldChange>, ContractError> {
+        let mut application = load_pending_application(storage, id)?;
+        // address might have been whitelisted since the application was submitted
+        if let Some(entry) = whitelist().may_load(storage, &application.address)? {
+            return Err(ContractError::AlreadyWhitelisted(
+                application.address.to_string(),
+                entry.protocol,
+            ));
+        }
+
+        let mut recipient_changes = vec![];
+        if let Some(weight) = weight {
+            recipient_changes.extend(save_weight(storage, &application.protocol, Some(weight))?);
+        } else if WEIGHTS.may_load(storage, &application.protocol)?.is_none() {
+            return Err(ContractError::MissingProtocol(application.protocol));
+        }
+        recipient_changes.extend(save_whitelist_entry(
+            storage,
+            &application.address,
+            Some(Whitelist {
+                address: application.address.clone(),
+                protocol: application.protocol.clone(),
+                destination: None,
+            }),
+        )?);
+
+        application.status = ApplicationStatus::Approved;
+        APPLICATIONS.save(storage, U64Key::new(id), &application)?;
+        Ok(recipient_changes)
+    }
+
+    pub fn reject_application(
+        deps: DepsMut,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_admin(deps.api, &config, &info.sender)?;
+        let mut application = load_pending_application(deps.storage, id)?;
+
+        application.status = ApplicationStatus::Rejected;
+        APPLICATIONS.save(deps.storage, U64Key::new(id), &application)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "reject_application")
+            .add_attribute("id", id.to_string()))
+    }
+
+    pub fn change_my_address(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_address: String,
+    ) -> Result<Response, ContractError> {
+        let new_address = deps.api.addr_validate(&new_address)?;
+        let config = CONFIG.load(deps.storage)?;
+
+        let response = Response::new()
+            .add_attribute("action", "change_my_address")
+            .add_attribute("old_address", info.sender.to_string())
+            .add_attribute("new_address", new_address.to_string());
+
+        if config.address_change_requires_accept {
+            check_rotation(deps.storage, &info.sender, &new_address)?;
+            PENDING_ADDRESS_CHANGES.save(deps.storage, &info.sender, &new_address)?;
+            return Ok(response.add_attribute("pending", "true"));
+        }
+
+        let mut new_config = config.clone();
+        let recipient_changes =
+            rotate_address(deps.storage, &mut new_config, &info.sender, &new_address)?;
+        let event = save_config(
+            deps,
+            &env,
+            &info.sender,
+            "change_my_address",
+            &config,
+            &new_config,
+            recipient_changes,
+        )?;
+        Ok(response.add_events(event))
+    }
+
+    pub fn accept_address_change(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        old_address: String,
+    ) -> Result<Response, ContractError> {
+        let old_address = deps.api.addr_validate(&old_address)?;
+        let new_address = PENDING_ADDRESS_CHANGES
+            .may_load(deps.storage, &old_address)?
+            .ok_or_else(|| ContractError::NoPendingAddressChange(old_address.to_string()))?;
+        if new_address != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        PENDING_ADDRESS_CHANGES.remove(deps.storage, &old_address);
+
+        let config = CONFIG.load(deps.storage)?;
+        let mut new_config = config.clone();
+        // whitelist might have changed since the address change was proposed
+        let recipient_changes =
+            rotate_address(deps.storage, &mut new_config, &old_address, &new_address)?;
+        let event = save_config(
+            deps,
+            &env,
+            &info.sender,
+            "accept_address_change",
+            &config,
+            &new_config,
+            recipient_changes,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "accept_address_change")
+            .add_attribute("old_address", old_address)
+            .add_attribute("new_address", new_address)
+            .add_events(event))
+    }
+
+    /// Returns whitelist entry of `old`, making sure it can be moved to `new`
+    fn check_rotation(
+        storage: &dyn Storage,
+        old: &Addr,
+        new: &Addr,
+    ) -> Result<Whitelist, ContractError> {
+        let entry = whitelist()
+            .may_load(storage, old)?
+            .ok_or_else(|| ContractError::NotWhitelisted(old.to_string()))?;
+        if let Some(existing) = whitelist().may_load(storage, new)? {
+            return Err(ContractError::AlreadyWhitelisted(
+                new.to_string(),
+                existing.protocol,
+            ));
+        }
+        // payouts to another address would keep going there after rotation
+        if let Some(Destination::Address { address } | Destination::Contract { address, .. }) =
+            &entry.destination
+        {
+            if address != old {
+                return Err(ContractError::SeparateDestination(
+                    old.to_string(),
+                    address.to_string(),
+                ));
+            }
+        }
+        Ok(entry)
+    }
+
+    /// Moves whitelist entry of `old` to `new` and replaces `old` in its payout destination
+    /// and in address caps of `config`. Usage of rotated caps is moved to the new address,
+    /// so caps cannot be reset by rotation. Returns whitelist changes.
+    fn rotate_address(
+        storage: &mut dyn Storage,
+        config: &mut Config,
+        old: &Addr,
+        new: &Addr,
+    ) -> Result<Vec<FieldChange>, ContractError> {
+        let mut entry = check_rotation(storage, old, new)?;
+        entry.address = new.clone();
+        // `check_rotation` makes sure address destination points to `old`
+        if let Some(Destination::Address { address } | Destination::Contract { address, .. }) =
+            &mut entry.destination
+        {
+            *address = new.clone();
+        }
+        let mut changes = vec![];
+        changes.extend(save_whitelist_entry(storage, old, None)?);
+        changes.extend(save_whitelist_entry(storage, new, Some(entry))?);
+
+        for cap in config.recipient_caps.iter_mut() {
+            if let CapTarget::Address { address } = &cap.target {
+                if address != old {
+                    continue;
+                }
+                let new_target = CapTarget::Address {
+                    address: new.clone(),
+                };
+                let old_key = cap.target.key();
+                if let Some(usage) = CAP_USAGE.may_load(storage, (&old_key, &cap.denom))? {
+                    CAP_USAGE.remove(storage, (&old_key, &cap.denom));
+                    CAP_USAGE.save(storage, (&new_target.key(), &cap.denom), &usage)?;
+                }
+                cap.target = new_target;
+            }
+        }
+        Ok(changes)
+    }
+
+    pub fn member_changed_hook(
+        deps: DepsMut,
+        info: MessageInfo,
+        hook: MemberChangedHookMsg,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if config.whitelist_group.as_ref() != Some(&info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let response = Response::new().add_attribute("action", "member_changed_hook");
+        let mut snapshot = match GROUP_SNAPSHOT.may_load(deps.storage)? {
+            Some(snapshot) if snapshot.group == info.sender => snapshot,
+            // snapshot of this group is not loaded yet, it will be with the next distribution
+            _ => return Ok(response),
+        };
+        for diff in hook.diffs {
+            let member = Addr::unchecked(diff.key);
+            snapshot.total_weight = snapshot
+                .total_weight
+                .saturating_sub(diff.old.unwrap_or_default())
+                + diff.new.unwrap_or_default();
+            match diff.new {
+                Some(weight) if weight > 0 => GROUP_MEMBERS.save(deps.storage, &member, &weight)?,
+                _ => GROUP_MEMBERS.remove(deps.storage, &member),
+            }
+        }
+        GROUP_SNAPSHOT.save(deps.storage, &snapshot)?;
+
+        Ok(response.add_attribute("total_weight", snapshot.total_weight.to_string()))
+    }
+
+    pub fn refresh_group_snapshot(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let group = config
+            .whitelist_group
+            .ok_or(ContractError::NoWhitelistGroup {})?;
+        let snapshot = load_group_snapshot(deps.storage, &deps.querier, &env, &group)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "refresh_group_snapshot")
+            .add_attribute("total_weight", snapshot.total_weight.to_string()))
+    }
+
+    /// Replaces cached group membership with members queried page by page from the group
+    fn load_group_snapshot(
+        storage: &mut dyn Storage,
+        querier: &QuerierWrapper,
+        env: &Env,
+        group: &Addr,
+    ) -> StdResult<GroupSnapshot> {
+        let cached: Vec<_> = GROUP_MEMBERS
+            .keys(storage, None, None, Order::Ascending)
+            .collect();
+        for key in cached {
+            GROUP_MEMBERS.remove(storage, &Addr::unchecked(String::from_utf8(key)?));
+        }
+
+        let contract = Cw4Contract(group.clone());
+        let mut total_weight = 0;
+        let mut start_after = None;
+        loop {
+            let members = contract.list_members(querier, start_after, Some(GROUP_PAGE_SIZE))?;
+            for member in members.iter().filter(|member| member.weight > 0) {
+                total_weight += member.weight;
+                GROUP_MEMBERS.save(storage, &Addr::unchecked(&member.addr), &member.weight)?;
+            }
+            match members.last() {
+                Some(last) if members.len() == GROUP_PAGE_SIZE as usize => {
+                    start_after = Some(last.addr.clone())
+                }
+                _ => break,
+            }
+        }
+
+        let snapshot = GroupSnapshot {
+            group: group.clone(),
+            total_weight,
+            height: env.block.height,
+        };
+        GROUP_SNAPSHOT.save(storage, &snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Returns voting weight of `voter` at given height, `None` if it is not a voter
+    fn voting_weight(
+        querier: &QuerierWrapper,
+        voting: &WeightVoting,
+        voter: &Addr,
+        height: u64,
+    ) -> StdResult<Option<u64>> {
+        match &voting.voters {
+            VoterSet::Fixed { voters } => Ok(voters
+                .iter()
+                .find(|entry| entry.address == *voter)
+                .map(|entry| entry.weight)),
+            VoterSet::Group { contract } => Cw4Contract(contract.clone())
+                .member_at_height(querier, voter.to_string(), height)
+                .map(|weight| weight.filter(|weight| *weight > 0)),
+        }
+    }
+
+    fn total_voting_weight(querier: &QuerierWrapper, voting: &WeightVoting) -> StdResult<u64> {
+        match &voting.voters {
+            VoterSet::Fixed { voters } => Ok(voters.iter().map(|entry| entry.weight).sum()),
+            VoterSet::Group { contract } => Cw4Contract(contract.clone()).total_weight(querier),
+        }
+    }
+
+    pub fn propose_weights(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        weights: Vec<crate::msg::WeightPerProtocol>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let voting = config
+            .weight_voting
+            .as_ref()
+            .ok_or(ContractError::WeightVotingDisabled {})?;
+        let height = env.block.height;
+        let weight = voting_weight(&deps.querier, voting, &info.sender, height)?
+            .ok_or(ContractError::Unauthorized {})?;
+        let total_weight = total_voting_weight(&deps.querier, voting)?;
+        let weights = convert_weights(weights);
+        validate_proposed_weights(deps.storage, &weights)?;
+
+        let id = WEIGHT_PROPOSAL_COUNT
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        WEIGHT_PROPOSAL_COUNT.save(deps.storage, &(id + 1))?;
+        let proposal = WeightProposal {
+            id,
+            proposer: info.sender.clone(),
+            weights,
+            start_height: height,
+            expires: env.block.time.plus_seconds(voting.voting_period),
+            threshold: voting.threshold,
+            total_weight,
+            yes: 0,
+            no: 0,
+            status: ProposalStatus::Open,
+        };
+
+        let response = Response::new()
+            .add_attribute("action", "propose_weights")
+            .add_attribute("proposal_id", id.to_string());
+        cast_vote(
+            deps,
+            &env,
+            &info.sender,
+            proposal,
+            Vote::Yes,
+            weight,
+            response,
+        )
+    }
+
+    pub fn vote_weights(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+        vote: Vote,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let voting = config
+            .weight_voting
+            .as_ref()
+            .ok_or(ContractError::WeightVotingDisabled {})?;
+        let proposal = WEIGHT_PROPOSALS
+            .may_load(deps.storage, U64Key::new(proposal_id))?
+            .ok_or(ContractError::NoSuchWeightProposal(proposal_id))?;
+        if proposal.current_status(env.block.time) != ProposalStatus::Open {
+            return Err(ContractError::WeightProposalNotOpen(proposal_id));
+        }
+        if WEIGHT_BALLOTS
+            .may_load(deps.storage, (U64Key::new(proposal_id), &info.sender))?
+            .is_some()
+        {
+            return Err(ContractError::AlreadyVoted(proposal_id));
+        }
+        let weight = voting_weight(&deps.querier, voting, &info.sender, proposal.start_height)?
+            .ok_or(ContractError::Unauthorized {})?;
+
+        let response = Response::new()
+            .add_attribute("action", "vote_weights")
+            .add_attribute("proposal_id", proposal_id.to_string());
+        cast_vote(deps, &env, &info.sender, proposal, vote, weight, response)
+    }
+
+    /// Makes sure that proposed weights cover every whitelisted protocol
+    /// and don't sum up to more than 1
+    fn validate_proposed_weights(
+        storage: &dyn Storage,
+        weights: &[WeightPerProtocol],
+    ) -> Result<(), ContractError> {
+        for wl_item in load_whitelist(storage)? {
+            if !weights
+                .iter()
+                .any(|entry| entry.protocol == wl_item.protocol)
+            {
+                return Err(ContractError::MissingProtocol(wl_item.protocol));
+            }
+        }
+        let total = weights
+            .iter()
+            .fold(Decimal::zero(), |total, entry| total + entry.weight);
+        if total > Decimal::one() {
+            return Err(ContractError::ProposedWeightsExceedOne {});
+        }
+        Ok(())
+    }
+
+    /// Records the ballot and updates tally. Applies proposed weights to config once
+    /// enough yes votes are collected.
+    fn cast_vote(
+        deps: DepsMut,
+        env: &Env,
+        voter: &Addr,
+        mut proposal: WeightProposal,
+        vote: Vote,
+        weight: u64,
+        response: Response,
+    ) -> Result<Response, ContractError> {
+        let id = proposal.id;
+        WEIGHT_BALLOTS.save(
+            deps.storage,
+            (U64Key::new(id), voter),
+            &Ballot { vote, weight },
+        )?;
+        match vote {
+            Vote::Yes => proposal.yes += weight,
+            Vote::No => proposal.no += weight,
+        }
+
+        let mut response = response
+            .add_attribute("voter", voter.to_string())
+            .add_attribute("vote", format!("{:?}", vote).to_lowercase());
+
+        if proposal.is_passed() {
+            // whitelist might have changed since the proposal was made
+            validate_proposed_weights(deps.storage, &proposal.weights)?;
+            proposal.status = ProposalStatus::Passed;
+            WEIGHT_PROPOSALS.save(deps.storage, U64Key::new(id), &proposal)?;
+
+            let config = CONFIG.load(deps.storage)?;
+            let recipient_changes = replace_weights(deps.storage, proposal.weights.clone())?;
+            let event = save_config(
+                deps,
+                env,
+                voter,
+                "weight_proposal_passed",
+                &config,
+                &config,
+                recipient_changes,
+            )?;
+            response = response.add_events(event);
+        } else {
+            proposal.status = proposal.current_status(env.block.time);
+            WEIGHT_PROPOSALS.save(deps.storage, U64Key::new(id), &proposal)?;
+        }
+
+        Ok(response.add_attribute("status", format!("{:?}", proposal.status).to_lowercase()))
+    }
+
+    pub fn retry_ibc_transfer(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+        let transfer = IBC_ESCROW
+            .may_load(deps.storage, U64Key::new(id))?
+            .ok_or(ContractError::NoSuchIbcTransfer(id))?;
+        IBC_ESCROW.remove(deps.storage, U64Key::new(id));
+        IBC_ESCROW_TOTAL.update(
+            deps.storage,
+            &transfer.amount.denom,
+            |total| -> StdResult<_> {
+                Ok(total
+                    .unwrap_or_default()
+                    .checked_sub(transfer.amount.amount)?)
+            },
+        )?;
+
+        let sent = deduct_tax(&transfer.amount, &deps.querier)?;
+        let msg = ibc_transfer_msg(
+            deps.storage,
+            &env,
+            IbcTransfer {
+                amount: sent,
+                ..transfer
+            },
+        )?;
+        Ok(Response::new()
+            .add_attribute("action", "retry_ibc_transfer")
+            .add_attribute("id", id.to_string())
+            .add_submessage(msg))
+    }
+}
+
+/// Privileged messages sent by chain governance
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::ForceUpdateConfig { change } => sudo::force_update_config(deps, env, *change),
+        SudoMsg::Pause { paused } => sudo::pause(deps, paused),
+        SudoMsg::SetAdmin { admin } => sudo::set_admin(deps, env, admin),
+        SudoMsg::Sweep {
+            denom,
+            amount,
+            recipient,
+        } => sudo::sweep(deps, env, denom, amount, recipient),
+        SudoMsg::IbcLifecycleComplete(outcome) => sudo::ibc_lifecycle_complete(deps, outcome),
+    }
+}
+
+mod sudo {
+    use super::*;
+
+    use super::execute::{apply_config_change, apply_recipients_change, save_config};
+
+    /// Applies config change bypassing admin and timelock
+    pub fn force_update_config(
+        deps: DepsMut,
+        env: Env,
+        change: ConfigChange,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let recipient_changes = apply_recipients_change(deps.storage, deps.api, &change)?;
+        let mut new_config = config.clone();
+        apply_config_change(deps.api, &mut new_config, change)?;
+        // governance has no address, so the revision is attributed to the contract itself
+        let sender = env.contract.address.clone();
+        let event = save_config(
+            deps,
+            &env,
+            &sender,
+            "sudo_force_update_config",
+            &config,
+            &new_config,
+            recipient_changes,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "sudo_force_update_config")
+            .add_events(event))
+    }
+
+    /// Kept apart from the admin pause, so the admin cannot resume the contract paused by governance
+    pub fn pause(deps: DepsMut, paused: bool) -> Result<Response, ContractError> {
+        GOVERNANCE_PAUSED.save(deps.storage, &paused)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "sudo_pause")
+            .add_attribute("paused", paused.to_string()))
+    }
+
+    pub fn set_admin(deps: DepsMut, env: Env, admin: String) -> Result<Response, ContractError> {
+        force_update_config(
+            deps,
+            env,
+            ConfigChange {
+                admin: Some(admin),
+                ..ConfigChange::default()
+            },
+        )
+    }
+
+    pub fn sweep(
+        deps: DepsMut,
+        env: Env,
+        denom: String,
+        amount: Option<Uint128>,
+        recipient: String,
+    ) -> Result<Response, ContractError> {
+        // governance has no address, so the sweep is attributed to the contract itself
+        let sender = env.contract.address.clone();
+        execute::sweep(deps, &env, &sender, "sudo_sweep", denom, amount, recipient)
+    }
+
+    /// Forgets acknowledged transfer, escrows failed or timed out one. By now the transfer
+    /// module has refunded the amount of the latter to the contract.
+    pub fn ibc_lifecycle_complete(
+        deps: DepsMut,
+        outcome: IbcLifecycleComplete,
+    ) -> Result<Response, ContractError> {
+        let (channel, sequence, error) = match outcome {
+            IbcLifecycleComplete::IbcAck {
+                channel,
+                sequence,
+                ack,
+                success,
+            } => (channel, sequence, if success { None } else { Some(ack) }),
+            IbcLifecycleComplete::IbcTimeout { channel, sequence } => {
+                (channel, sequence, Some("timeout".to_owned()))
+            }
+        };
+
+        let key = (channel.as_str(), U64Key::new(sequence));
+        let transfer = IBC_PACKETS
+            .may_load(deps.storage, key.clone())?
+            .ok_or_else(|| ContractError::NoSuchIbcPacket(channel.clone(), sequence))?;
+        IBC_PACKETS.remove(deps.storage, key);
+
+        match error {
+            Some(error) => escrow_ibc_transfer(deps.storage, transfer, error),
+            None => Ok(Response::new()
+                .add_attribute("action", "ibc_transfer_acknowledged")
+                .add_attribute("channel", channel)
+                .add_attribute("sequence", sequence.to_string())),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        IBC_TRANSFER_REPLY_ID => reply::ibc_transfer(deps, msg.result),
+        SWAP_REPLY_ID => reply::swap(deps, env),
+        BUYBACK_REPLY_ID => reply::buyback(deps, env),
+        id => Err(ContractError::UnknownReplyId(id)),
+    }
+}
+
+mod reply {
+    use super::*;
+
+    use cosmwasm_std::{coin, ContractResult, SubMsgExecutionResponse};
+
+    /// Splits tokens received from the swap dispatched by `Distribute`
+    pub fn swap(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let swap = PENDING_SWAP.load(deps.storage)?;
+        PENDING_SWAP.remove(deps.storage);
+
+        let balance = query_available_balance(deps.as_ref(), &env, &swap.ask_denom)?;
+        let received = balance.saturating_sub(swap.balance_before);
+        if received.is_zero() {
+            return Err(ContractError::NoBalance {});
+        }
+
+        let config = CONFIG.load(deps.storage)?;
+        let response = Response::new()
+            .add_attribute("action", "distribute_swapped")
+            .add_attribute("offer", swap.offer.to_string())
+            .add_attribute("received", received);
+        execute::split(deps, &env, &config, swap.ask_denom, received, response)
+    }
+
+    /// Burns tokens bought back by the burn share swap. Bought amount is the increase of
+    /// `target_denom` balance, so it doesn't depend on events emitted by the pair.
+    pub fn buyback(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let buyback = PENDING_BUYBACK.load(deps.storage)?;
+        PENDING_BUYBACK.remove(deps.storage);
+
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &buyback.target_denom)?
+            .amount;
+        let bought = balance.saturating_sub(buyback.balance_before);
+        if bought.is_zero() {
+            return Err(ContractError::NothingBoughtBack(buyback.target_denom));
+        }
+
+        let bought = coin(bought.u128(), buyback.target_denom);
+        Ok(Response::new()
+            .add_attribute("action", "buyback_and_burn")
+            .add_attribute("bought_back", bought.to_string())
+            .add_message(BankMsg::Burn {
+                amount: vec![bought],
+            }))
+    }
+
+    /// Escrows transfer rejected on dispatch, so it can be retried later.
+    /// Packets which time out or fail on the remote chain are refunded to the contract
+    /// balance by the transfer module and are paid out again by the next `Distribute`.
+    pub fn ibc_transfer(
+        deps: DepsMut,
+        result: ContractResult<SubMsgExecutionResponse>,
+    ) -> Result<Response, ContractError> {
+        let mut in_flight = IBC_TRANSFERS_IN_FLIGHT.load(deps.storage)?;
+        // replies come in the same order in which transfers were dispatched
+        let transfer = in_flight.remove(0);
+        if in_flight.is_empty() {
+            IBC_TRANSFERS_IN_FLIGHT.remove(deps.storage);
+        } else {
+            IBC_TRANSFERS_IN_FLIGHT.save(deps.storage, &in_flight)?;
+        }
+
+        let response = match result {
+            ContractResult::Ok(response) => response,
+            ContractResult::Err(error) => {
+                return escrow_ibc_transfer(deps.storage, transfer, error)
+            }
+        };
+
+        // keep the transfer until its outcome is reported, so refunds can be escrowed
+        let packet = response
+            .events
+            .iter()
+            .find(|event| event.ty == "send_packet")
+            .and_then(|event| {
+                let attribute = |key: &str| {
+                    event
+                        .attributes
+                        .iter()
+                        .find(|attr| attr.key == key)
+                        .map(|attr| attr.value.clone())
+                };
+                Some((
+                    attribute("packet_src_channel")?,
+                    attribute("packet_sequence")?.parse::<u64>().ok()?,
+                ))
+            });
+        let (channel, sequence) = match packet {
+            Some(packet) => packet,
+            None => return Ok(Response::new()),
+        };
+        IBC_PACKETS.save(deps.storage, (&channel, U64Key::new(sequence)), &transfer)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "send_ibc_transfer")
+            .add_attribute("channel", channel)
+            .add_attribute("sequence", sequence.to_string()))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query::config(deps)?),
+        QueryMsg::AdminInfo {} => to_binary(&query::admin_info(deps)?),
+        QueryMsg::Paused {} => to_binary(&query::paused(deps)?),
+        QueryMsg::PendingConfigChange {} => to_binary(&query::pending_config_change(deps)?),
+        QueryMsg::ConfigHistory { start_after, limit } => {
+            to_binary(&query::config_history(deps, start_after, limit)?)
+        }
+        QueryMsg::Epoch { denom } => to_binary(&query::epoch(deps, env, denom)?),
+        QueryMsg::DeveloperVesting {} => to_binary(&query::developer_vesting(deps, env)?),
+        QueryMsg::GroupMembers { start_after, limit } => {
+            to_binary(&query::group_members(deps, start_after, limit)?)
+        }
+        QueryMsg::WeightProposal { proposal_id } => {
+            to_binary(&query::weight_proposal(deps, env, proposal_id)?)
+        }
+        QueryMsg::WeightProposals { start_after, limit } => {
+            to_binary(&query::weight_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::WeightVote { proposal_id, voter } => {
+            to_binary(&query::weight_vote(deps, proposal_id, voter)?)
+        }
+        QueryMsg::PendingAddressChange { address } => {
+            to_binary(&query::pending_address_change(deps, address)?)
+        }
+        QueryMsg::PendingApplications { start_after, limit } => {
+            to_binary(&query::pending_applications(deps, start_after, limit)?)
+        }
+        QueryMsg::CapUsage { denom } => to_binary(&query::cap_usage(deps, env, denom)?),
+        QueryMsg::IbcEscrow { start_after, limit } => {
+            to_binary(&query::ibc_escrow(deps, start_after, limit)?)
+        }
+        QueryMsg::Whitelist {
+            protocol,
+            start_after,
+            limit,
+        } => to_binary(&query::whitelist(deps, protocol, start_after, limit)?),
+        QueryMsg::WeightPerProtocol {} => to_binary(&query::weight_per_protocol(deps)?),
+        QueryMsg::PendingDistribution {} => to_binary(&query::pending_distribution(deps)?),
+        QueryMsg::Contributions { address } => to_binary(&query::contributions(deps, address)?),
+        QueryMsg::TopContributors { denom, limit } => {
+            to_binary(&query::top_contributors(deps, denom, limit)?)
+        }
+    }
+}
+
+mod query {
+    use super::*;
+
+    use cosmwasm_std::Addr;
+    use cw3::Cw3QueryMsg;
+    use cw4::Member;
+    use cw_storage_plus::Bound;
+
+    // settings for pagination
+    const MAX_LIMIT: u32 = 30;
+    const DEFAULT_LIMIT: u32 = 10;
+
+    pub fn config(deps: Deps) -> StdResult<Config> {
+        CONFIG.load(deps.storage)
+    }
+
+    pub fn paused(deps: Deps) -> StdResult<PausedResponse> {
+        Ok(PausedResponse {
+            paused: is_paused(deps.storage)?,
+            governance_paused: GOVERNANCE_PAUSED
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn admin_info(deps: Deps) -> StdResult<AdminInfoResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        if config.admin.is_empty() {
+            return Ok(AdminInfoResponse {
+                admin: None,
+                admin_type: AdminType::None {},
+            });
+        }
+
+        let admin = deps.api.addr_validate(&config.admin)?;
+        // Anything answering CW3 threshold query is treated as CW3 multisig or DAO
+        let admin_type = match deps
+            .querier
+            .query_wasm_smart(admin.to_string(), &Cw3QueryMsg::Threshold {})
+        {
+            Ok(threshold) => AdminType::Cw3 { threshold },
+            Err(_) => AdminType::Address {},
+        };
+        Ok(AdminInfoResponse {
+            admin: Some(admin),
+            admin_type,
+        })
+    }
+
+    pub fn pending_config_change(deps: Deps) -> StdResult<Option<PendingConfigChange>> {
+        PENDING_CONFIG_CHANGE.may_load(deps.storage)
+    }
+
+    pub fn config_history(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ConfigHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive_int);
+
+        let revisions = CONFIG_HISTORY
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, revision)| revision))
+            .collect::<StdResult<_>>()?;
+
+        Ok(ConfigHistoryResponse { revisions })
+    }
+
+    pub fn epoch(deps: Deps, env: Env, denom: String) -> StdResult<EpochResponse> {
+        let epoch = EPOCHS.may_load(deps.storage, &denom)?;
+        let releasable = epoch
+            .as_ref()
+            .map(|epoch| epoch.releasable(env.block.height))
+            .unwrap_or_default();
+        Ok(EpochResponse { epoch, releasable })
+    }
+
+    pub fn developer_vesting(deps: Deps, env: Env) -> StdResult<DeveloperVestingResponse> {
+        let schedule = CONFIG.load(deps.storage)?.developer_vesting;
+        let balances = DEVELOPER_VESTING
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (denom, VestingBalance { accrued, claimed }) = item?;
+                let vested = match &schedule {
+                    Some(schedule) => schedule.vested(accrued, env.block.time),
+                    None => accrued,
+                };
+                Ok(DeveloperVestingBalance {
+                    denom: String::from_utf8(denom)?,
+                    accrued,
+                    vested,
+                    unvested: accrued - vested,
+                    claimed,
+                    claimable: vested.saturating_sub(claimed),
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(DeveloperVestingResponse { schedule, balances })
+    }
+
+    pub fn group_members(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<GroupMembersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|address| deps.api.addr_validate(&address))
+            .transpose()?;
+        let start = start_after
+            .as_ref()
+            .map(|address| Bound::exclusive(address.as_bytes()));
+
+        let members = GROUP_MEMBERS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (address, weight) = item?;
+                Ok(Member {
+                    addr: String::from_utf8(address)?,
+                    weight,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(GroupMembersResponse {
+            snapshot: GROUP_SNAPSHOT.may_load(deps.storage)?,
+            members,
+        })
+    }
+
+    fn weight_proposal_response(env: &Env, proposal: WeightProposal) -> WeightProposalResponse {
+        WeightProposalResponse {
+            status: proposal.current_status(env.block.time),
+            proposal,
+        }
+    }
+
+    pub fn weight_proposal(
+        deps: Deps,
+        env: Env,
+        proposal_id: u64,
+    ) -> StdResult<WeightProposalResponse> {
+        let proposal = WEIGHT_PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+        Ok(weight_proposal_response(&env, proposal))
+    }
+
+    pub fn weight_proposals(
+        deps: Deps,
+        env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<WeightProposalsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive_int);
+
+        let proposals = WEIGHT_PROPOSALS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, proposal)| weight_proposal_response(&env, proposal)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(WeightProposalsResponse { proposals })
+    }
+
+    pub fn weight_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Option<Ballot>> {
+        let voter = deps.api.addr_validate(&voter)?;
+        WEIGHT_BALLOTS.may_load(deps.storage, (U64Key::new(proposal_id), &voter))
+    }
+
+    pub fn pending_address_change(deps: Deps, address: String) -> StdResult<Option<Addr>> {
+        let address = deps.api.addr_validate(&address)?;
+        PENDING_ADDRESS_CHANGES.may_load(deps.storage, &address)
+    }
+
+    pub fn pending_applications(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ApplicationsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive_int);
+
+        let applications = APPLICATIONS
+            .range(deps.storage, start, None, Order::Ascending)
+            .map(|item| item.map(|(_, application)| application))
+            // skip already decided applications, but keep errors
+            .filter(|item| match item {
+                Ok(application) => application.status == ApplicationStatus::Pending,
+                Err(_) => true,
+            })
+            .take(limit)
+            .collect::<StdResult<_>>()?;
+
+        Ok(ApplicationsResponse { applications })
+    }
+
+    pub fn cap_usage(deps: Deps, env: Env, denom: String) -> StdResult<CapUsageResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let caps = config
+            .recipient_caps
+            .iter()
+            .filter(|cap| cap.denom == denom)
+            .map(|cap| {
+                let received = load_cap_usage(deps.storage, &env, &config, cap)?.received();
+                Ok(CapUsageEntry {
+                    cap: cap.clone(),
+                    received,
+                    remaining: cap.per_window.map(|limit| limit.saturating_sub(received)),
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(CapUsageResponse { caps })
+    }
+
+    pub fn ibc_escrow(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<IbcEscrowResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive_int);
+
+        let transfers = IBC_ESCROW
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (id, transfer) = item?;
+                Ok(EscrowedIbcTransfer {
+                    id: u64::from_be_bytes(
+                        id.as_slice()
+                            .try_into()
+                            .map_err(|_| StdError::generic_err("Invalid escrow id"))?,
+                    ),
+                    transfer,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(IbcEscrowResponse { transfers })
+    }
+
+    pub fn whitelist(
+        deps: Deps,
+        protocol: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<WhitelistResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after
+            .map(|address| deps.api.addr_validate(&address))
+            .transpose()?
+            .map(|address| Bound::exclusive(address.as_bytes()));
+
+        let entries = crate::state::whitelist();
+        let range = match protocol {
+            Some(protocol) => entries.idx.protocol.prefix(protocol.into_bytes()).range(
+                deps.storage,
+                start,
+                None,
+                Order::Ascending,
+            ),
+            None => entries.range(deps.storage, start, None, Order::Ascending),
+        };
+        let whitelist = range
+            .take(limit)
+            .map(|item| item.map(|(_, entry)| entry))
+            .collect::<StdResult<_>>()?;
+
+        Ok(WhitelistResponse { whitelist })
+    }
+
+    pub fn weight_per_protocol(deps: Deps) -> StdResult<WeightPerProtocolResponse> {
+        Ok(WeightPerProtocolResponse {
+            weight_per_protocol: load_weights(deps.storage)?,
+        })
+    }
+
+    pub fn pending_distribution(deps: Deps) -> StdResult<Option<PendingDistribution>> {
+        PENDING_DISTRIBUTION.may_load(deps.storage)
+    }
+
+    pub fn contributions(deps: Deps, address: String) -> StdResult<ContributionsResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let contributions = crate::state::contributions()
+            .prefix(&address)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, contribution)| contribution))
+            .collect::<StdResult<_>>()?;
+
+        Ok(ContributionsResponse { contributions })
+    }
+
+    pub fn top_contributors(
+        deps: Deps,
+        denom: String,
+        limit: Option<u32>,
+    ) -> StdResult<ContributionsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let contributions = crate::state::contributions()
+            .idx
+            .deposited
+            .sub_prefix(denom.into_bytes())
+            .range(deps.storage, None, None, Order::Descending)
+            .take(limit)
+            .map(|item| item.map(|(_, contribution)| contribution))
+            .collect::<StdResult<_>>()?;
+
+        Ok(ContributionsResponse { contributions })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let version = get_contract_version(deps.storage)?;
+    if version.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrate(
+            version.contract,
+            version.version,
+        ));
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", &version.version);
+    if version.version.starts_with("0.1.") {
+        let migrated_whitelist = migrate_v0_1(deps.api, deps.storage)?;
+        response = response.add_attribute("migrated_whitelist", migrated_whitelist.to_string());
+    } else if version.version != CONTRACT_VERSION {
+        return Err(ContractError::CannotMigrate(
+            version.contract,
+            version.version,
+        ));
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(response)
+}
+
+/// Moves whitelist and weights embedded in v0.1 config to their own maps and fills config
+/// fields added since then with the same defaults `instantiate` uses.
+/// Whitelist is validated like a new one, so duplicated addresses fail the migration instead
+/// of being silently merged. Returns number of stored whitelist entries.
+fn migrate_v0_1(api: &dyn Api, storage: &mut dyn Storage) -> Result<usize, ContractError> {
+    let old = CONFIG_V0_1.load(storage)?;
+    let whitelist = validate_whitelist(
+        api,
+        old.whitelist
+            .into_iter()
+            .map(|entry| crate::msg::Whitelist {
+                address: entry.address.into_string(),
+                protocol: entry.protocol,
+                destination: None,
+            })
+            .collect(),
+    )?;
+    let migrated_whitelist = whitelist.len();
+    replace_whitelist(storage, whitelist)?;
+    replace_weights(storage, old.weight_per_protocol)?;
+
+    let config = Config {
+        admin: old.admin,
+        burn_address: old.burn_address,
+        developer_address: old.developer_address,
+        percent_to_burn: old.percent_to_burn,
+        percent_to_developer: old.percent_to_developer,
+        percent_to_distribute: old.percent_to_distribute,
+        // v0.1 threshold was always in uluna
+        less_then_threshold: old.less_then_threshold,
+        threshold_denom: DEFAULT_THRESHOLD_DENOM.to_owned(),
+        min_distribution: Uint128::zero(),
+        config_change_delay: 0,
+        epoch_length: 0,
+        developer_vesting: None,
+        developer_destination: None,
+        burn_sinks: vec![],
+        recipient_caps: vec![],
+        cap_window: 0,
+        cap_overflow: CapOverflow::Burn,
+        address_change_requires_accept: false,
+        weight_voting: None,
+        whitelist_group: None,
+        deposit_denoms: vec![],
+        distribution_batch_size: 0,
+        // v0.1 never swapped, so distributed denoms are kept as they are
+        payout_denom: None,
+        swap_pairs: vec![],
+        max_spread: Decimal::percent(DEFAULT_MAX_SPREAD),
+        // v0.1 sent the whole burn share to `burn_address`
+        burn_strategy: BurnStrategy::Direct {},
+    };
+    CONFIG.save(storage, &config)?;
+    Ok(migrated_whitelist)
 }
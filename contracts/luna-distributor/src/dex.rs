@@ -0,0 +1,133 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Decimal, QuerierWrapper, Uint128, WasmMsg};
+use terra_cosmwasm::{TerraMsgWrapper, TerraQuerier};
+
+use crate::error::ContractError;
+
+/// Subset of terraswap compatible pair messages used to swap native tokens
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairExecuteMsg {
+    Swap {
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+/// Subset of terraswap compatible pair queries used to simulate swaps
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairQueryMsg {
+    Simulation { offer_asset: Asset },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    NativeToken { denom: String },
+}
+
+impl From<&Coin> for Asset {
+    fn from(coin: &Coin) -> Self {
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: coin.denom.clone(),
+            },
+            amount: coin.amount,
+        }
+    }
+}
+
+/// Simulates swap of `offer_coin` to `ask_denom` on the `pair` and creates message executing it.
+///
+/// `max_spread` bounds the price impact of the swap itself: the simulated spread is the
+/// difference between the pool price before the swap and the price the swap actually gets.
+/// It says nothing about whether the pool price is fair, as the simulation runs against the
+/// same pool the swap is executed on. A pool moved right before the distribution is
+/// simulated and swapped with the moved price. To bound that, the pool price is also compared
+/// with the oracle price, if the oracle quotes `ask_denom` for the offered denom.
+///
+/// Swapped tokens are sent back to the sender.
+pub fn swap_msg(
+    querier: &QuerierWrapper,
+    pair: &Addr,
+    offer_coin: Coin,
+    ask_denom: &str,
+    max_spread: Decimal,
+) -> Result<CosmosMsg<TerraMsgWrapper>, ContractError> {
+    let offer_asset = Asset::from(&offer_coin);
+    let simulation: SimulationResponse = querier.query_wasm_smart(
+        pair,
+        &PairQueryMsg::Simulation {
+            offer_asset: offer_asset.clone(),
+        },
+    )?;
+    let expected_return =
+        simulation.return_amount + simulation.spread_amount + simulation.commission_amount;
+    let spread = if expected_return.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(simulation.spread_amount, expected_return)
+    };
+    if spread > max_spread {
+        return Err(ContractError::MaxSpreadExceeded(
+            offer_coin.denom,
+            spread,
+            max_spread,
+        ));
+    }
+
+    // Denoms not quoted by the oracle (like IBC tokens) make the query fail
+    let oracle_rate = TerraQuerier::new(querier)
+        .query_exchange_rates(offer_coin.denom.as_str(), vec![ask_denom])
+        .ok()
+        .and_then(|rates| {
+            rates
+                .exchange_rates
+                .into_iter()
+                .find(|rate| rate.quote_denom == ask_denom)
+        });
+    if let Some(oracle_rate) = oracle_rate {
+        let oracle_return = offer_coin.amount * oracle_rate.exchange_rate;
+        if expected_return < oracle_return {
+            let deviation = Decimal::from_ratio(oracle_return - expected_return, oracle_return);
+            if deviation > max_spread {
+                return Err(ContractError::OraclePriceDeviation(
+                    offer_coin.denom,
+                    deviation,
+                    max_spread,
+                ));
+            }
+        }
+    }
+
+    let msg = PairExecuteMsg::Swap {
+        offer_asset,
+        belief_price: Some(Decimal::from_ratio(offer_coin.amount, expected_return)),
+        max_spread: Some(max_spread),
+        to: None,
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: pair.to_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![offer_coin],
+    }
+    .into())
+}
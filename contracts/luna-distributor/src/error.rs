@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError, Timestamp};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,12 +6,27 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("Cannot migrate from {0} version {1}")]
+    CannotMigrate(String, String),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("Contract has to be paused first")]
+    NotPaused {},
+
+    #[error("Cannot sweep {0}, only {1} is not reserved for pending payouts")]
+    SweepExceedsAvailable(u128, u128),
+
     #[error("Zero balance")]
     NoBalance {},
 
+    #[error("No funds sent")]
+    NoFunds {},
+
     #[error("Trying to deposit more then one denom at once")]
     DepositMoreThenOne {},
 
@@ -29,4 +44,109 @@ pub enum ContractError {
 
     #[error("Balance is bigger {0} then specified threshold {1}")]
     BurnTheBottom(u128, u128),
+
+    #[error("Distributed amount {0} is below minimal distribution {1}")]
+    BelowMinDistribution(u128, u128),
+
+    #[error("Oracle has no exchange rate of {0} to {1}")]
+    NoExchangeRate(String, String),
+
+    #[error("Swap of {0} exceeds max spread {2} with spread {1}")]
+    MaxSpreadExceeded(String, Decimal, Decimal),
+
+    #[error("Pair price of {0} is {1} below the oracle price, more than max spread {2}")]
+    OraclePriceDeviation(String, Decimal, Decimal),
+
+    #[error("Max spread has to be within [0, 1]")]
+    InvalidMaxSpread {},
+
+    #[error("Buyback returned no {0}")]
+    NothingBoughtBack(String),
+
+    #[error("Config changes are timelocked - use ProposeConfigChange instead")]
+    TimelockActive {},
+
+    #[error("There is already a pending config change")]
+    ConfigChangeAlreadyPending {},
+
+    #[error("There is no pending config change")]
+    NoPendingConfigChange {},
+
+    #[error("Pending config change can be executed after {0}")]
+    ConfigChangeNotExecutable(Timestamp),
+
+    #[error("Distribution of {0} is in progress - finish it with ContinueDistribution first")]
+    DistributionInProgress(String),
+
+    #[error("There is no distribution in progress")]
+    NoDistributionInProgress {},
+
+    #[error("Nothing has been unlocked in current epoch since last distribution")]
+    NothingUnlocked {},
+
+    #[error("There are no vested tokens to claim")]
+    NothingToClaim {},
+
+    #[error("Developer vesting cannot be set and disabled by the same config change")]
+    ConflictingDeveloperVesting {},
+
+    #[error("Weights of burn sinks have to sum up to 1")]
+    InvalidBurnSinkWeights {},
+
+    #[error("Stake destination needs validators with weights summing up to 1")]
+    InvalidValidatorWeights {},
+
+    #[error("Contract has no delegations")]
+    NoDelegations {},
+
+    #[error("There is no whitelist application with id {0}")]
+    NoSuchApplication(u64),
+
+    #[error("Whitelist application {0} has already been decided")]
+    ApplicationNotPending(u64),
+
+    #[error("Address {0} is already whitelisted for protocol {1}")]
+    AlreadyWhitelisted(String, String),
+
+    #[error("Address {0} is not whitelisted")]
+    NotWhitelisted(String),
+
+    #[error("There is no pending address change of {0}")]
+    NoPendingAddressChange(String),
+
+    #[error("Payouts of {0} go to separate address {1}, which has to be changed by the admin")]
+    SeparateDestination(String, String),
+
+    #[error("Whitelist group is not set")]
+    NoWhitelistGroup {},
+
+    #[error("Weight voting is not enabled")]
+    WeightVotingDisabled {},
+
+    #[error("Invalid weight voting - voters and voting period cannot be empty and threshold has to be within (0, 1]")]
+    InvalidWeightVoting {},
+
+    #[error("There is no weight proposal with id {0}")]
+    NoSuchWeightProposal(u64),
+
+    #[error("Weight proposal {0} is not open for voting")]
+    WeightProposalNotOpen(u64),
+
+    #[error("Proposed weights cannot sum up to more than 1")]
+    ProposedWeightsExceedOne {},
+
+    #[error("Already voted on weight proposal {0}")]
+    AlreadyVoted(u64),
+
+    #[error("Per window recipient caps require non-zero cap window")]
+    MissingCapWindow {},
+
+    #[error("There is no escrowed IBC transfer with id {0}")]
+    NoSuchIbcTransfer(u64),
+
+    #[error("There is no IBC transfer sent as packet {1} on {0}")]
+    NoSuchIbcPacket(String, u64),
+
+    #[error("Unknown reply id {0}")]
+    UnknownReplyId(u64),
 }
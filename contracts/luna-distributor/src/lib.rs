@@ -1,4 +1,6 @@
+mod community_pool;
 pub mod contract;
+mod dex;
 mod error;
 pub mod msg;
 #[cfg(test)]
@@ -1,7 +1,14 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Decimal;
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
+use cw3::ThresholdResponse;
+use cw4::{Member, MemberChangedHookMsg};
+
+use crate::state::{
+    Application, CapOverflow, ConfigRevision, Contribution, Epoch, GroupSnapshot, IbcTransfer,
+    ProposalStatus, VestingSchedule, Vote, WeightProposal,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
@@ -13,6 +20,9 @@ pub struct InstantiateMsg {
     pub developer_address: String,
     pub whitelist: Vec<Whitelist>,
     pub weight_per_protocol: Vec<WeightPerProtocol>,
+    /// Delay in seconds between proposing and executing a config change, no timelock if omitted
+    #[serde(default)]
+    pub config_change_delay: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -33,12 +43,353 @@ pub enum ExecuteMsg {
         /// Denom of leftover tokens to burn
         denom: String,
     },
+    /// Queue a config change which can be executed after `config_change_delay`
+    ProposeConfigChange {
+        change: Box<ConfigChange>,
+    },
+    /// Apply pending config change once its delay has passed
+    ExecuteConfigChange {},
+    /// Abort pending config change
+    CancelConfigChange {},
+    /// Send vested part of developer share to the developer address
+    ClaimDeveloperVested {},
+    /// Withdraw staking rewards of all contract delegations back to the contract,
+    /// so they are redistributed by `Distribute`
+    ClaimStakingRewards {},
+    /// Send escrowed IBC transfer again
+    RetryIbcTransfer {
+        id: u64,
+    },
+    /// Ask the admin to add `address` to the whitelist under `protocol`
+    ApplyForWhitelist {
+        protocol: String,
+        address: String,
+    },
+    /// Add applied address to the whitelist. `weight` sets weight of the protocol
+    /// and is required if the protocol has no weight yet.
+    ApproveApplication {
+        id: u64,
+        weight: Option<Decimal>,
+    },
+    RejectApplication {
+        id: u64,
+    },
+    /// Move whitelist entry of the sender to `new_address`
+    ChangeMyAddress {
+        new_address: String,
+    },
+    /// Accept address change proposed by `old_address`, sent from the new address
+    AcceptAddressChange {
+        old_address: String,
+    },
+    /// Called by the whitelist group when its members change
+    MemberChangedHook(MemberChangedHookMsg),
+    /// Reload whole membership of the whitelist group
+    RefreshGroupSnapshot {},
+    /// Propose new `weight_per_protocol`, proposer votes yes
+    ProposeWeights {
+        weights: Vec<WeightPerProtocol>,
+    },
+    VoteWeights {
+        proposal_id: u64,
+        vote: Vote,
+    },
+    /// Stop or resume paying out
+    SetPaused {
+        paused: bool,
+    },
+    /// Send `amount` of `denom` (whole balance if not set) to `recipient`, only while paused
+    Sweep {
+        denom: String,
+        amount: Option<Uint128>,
+        recipient: String,
+    },
+    /// Deposit single coin of an accepted denom, recording the contribution
+    Deposit {},
+    /// Pay out next `limit` (`distribution_batch_size` if not set) queued protocol payouts
+    ContinueDistribution {
+        limit: Option<u32>,
+    },
+}
+
+/// Messages sent by chain governance through the sudo entry point
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    /// Apply config change, bypassing admin and timelock
+    ForceUpdateConfig {
+        change: Box<ConfigChange>,
+    },
+    /// Stop or resume paying out
+    Pause {
+        paused: bool,
+    },
+    SetAdmin {
+        admin: String,
+    },
+    /// Send `amount` of `denom` (whole balance if not set) to `recipient`
+    Sweep {
+        denom: String,
+        amount: Option<Uint128>,
+        recipient: String,
+    },
+    /// Outcome of a sent IBC transfer, in the format of IBC hooks callbacks.
+    /// Failed and timed out transfers are refunded by the transfer module, and are escrowed
+    /// for retry.
+    IbcLifecycleComplete(IbcLifecycleComplete),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IbcLifecycleComplete {
+    IbcAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    IbcTimeout {
+        channel: String,
+        sequence: u64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
+    /// Returns admin and whether it is a CW3 multisig or DAO
+    AdminInfo {},
+    /// Returns whether paying out is paused
+    Paused {},
+    /// Returns config change waiting for execution, if any
+    PendingConfigChange {},
+    /// Lists config revisions in chronological order
+    ConfigHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns current distribution epoch of given denom
+    Epoch {
+        denom: String,
+    },
+    /// Returns vested and unvested developer share per denom
+    DeveloperVesting {},
+    /// Lists cached members of the whitelist group
+    GroupMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    WeightProposal {
+        proposal_id: u64,
+    },
+    /// Lists weight proposals in order of submission
+    WeightProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns vote of `voter` on the proposal, if any
+    WeightVote {
+        proposal_id: u64,
+        voter: String,
+    },
+    /// Returns address change proposed by whitelisted `address`, if any
+    PendingAddressChange {
+        address: String,
+    },
+    /// Lists whitelist applications waiting for admin decision
+    PendingApplications {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns caps configured for given denom with their usage in the current window
+    CapUsage {
+        denom: String,
+    },
+    /// Lists IBC transfers waiting for retry
+    IbcEscrow {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists whitelist entries ordered by address, optionally only ones of `protocol`
+    Whitelist {
+        protocol: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns weights of all protocols
+    WeightPerProtocol {},
+    /// Returns distribution being paid out in batches, if any
+    PendingDistribution {},
+    /// Returns amounts deposited by `address` per denom, with the part assigned to burning
+    /// at the time of deposit
+    Contributions {
+        address: String,
+    },
+    /// Returns biggest contributors of `denom`, sorted by deposited amount
+    TopContributors {
+        denom: String,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigHistoryResponse {
+    pub revisions: Vec<ConfigRevision>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochResponse {
+    pub epoch: Option<Epoch>,
+    /// Amount which would be paid out by `Distribute` at current block
+    pub releasable: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PausedResponse {
+    pub paused: bool,
+    /// Set if paused by governance, which only sudo can resume
+    pub governance_paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminInfoResponse {
+    pub admin: Option<Addr>,
+    pub admin_type: AdminType,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminType {
+    /// Config is not updatable
+    None {},
+    /// Admin is an account or a contract other than CW3
+    Address {},
+    /// Admin is CW3 multisig or DAO, config changes require its proposals to pass
+    Cw3 { threshold: ThresholdResponse },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GroupMembersResponse {
+    pub snapshot: Option<GroupSnapshot>,
+    pub members: Vec<Member>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WeightProposalResponse {
+    pub proposal: WeightProposal,
+    /// Status at current block time
+    pub status: ProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WeightProposalsResponse {
+    pub proposals: Vec<WeightProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApplicationsResponse {
+    pub applications: Vec<Application>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WhitelistResponse {
+    pub whitelist: Vec<crate::state::Whitelist>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WeightPerProtocolResponse {
+    pub weight_per_protocol: Vec<crate::state::WeightPerProtocol>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContributionsResponse {
+    pub contributions: Vec<Contribution>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcEscrowResponse {
+    pub transfers: Vec<EscrowedIbcTransfer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowedIbcTransfer {
+    pub id: u64,
+    pub transfer: IbcTransfer,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DeveloperVestingResponse {
+    pub schedule: Option<VestingSchedule>,
+    pub balances: Vec<DeveloperVestingBalance>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DeveloperVestingBalance {
+    pub denom: String,
+    /// Total developer share accrued on the contract
+    pub accrued: Uint128,
+    pub vested: Uint128,
+    pub unvested: Uint128,
+    pub claimed: Uint128,
+    /// Vested amount which can be claimed right now
+    pub claimable: Uint128,
+}
+
+/// Set of optional config updates. Fields left as `None` are not changed.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigChange {
+    pub admin: Option<String>,
+    pub burn_address: Option<String>,
+    pub developer_address: Option<String>,
+    pub whitelist: Option<Vec<Whitelist>>,
+    pub weight_per_protocol: Option<Vec<WeightPerProtocol>>,
+    pub config_change_delay: Option<u64>,
+    pub epoch_length: Option<u64>,
+    /// Threshold for `BurnTheBottom`, in `threshold_denom`
+    pub less_then_threshold: Option<Uint128>,
+    /// Base denom thresholds are expressed in, priced with oracle exchange rates
+    pub threshold_denom: Option<String>,
+    /// Minimal amount `Distribute` pays out at once, in `threshold_denom`
+    pub min_distribution: Option<Uint128>,
+    pub developer_vesting: Option<VestingSchedule>,
+    /// Turns developer vesting off, so developer share is paid out by `Distribute` again.
+    /// Share accrued so far can be claimed right away.
+    pub disable_developer_vesting: Option<bool>,
+    pub developer_destination: Option<Destination>,
+    /// Weighted destinations of the burn share, weights have to sum up to 1.
+    /// Empty list sends whole burn share to `burn_address`.
+    pub burn_sinks: Option<Vec<WeightedBurnSink>>,
+    pub recipient_caps: Option<Vec<RecipientCap>>,
+    /// Length in seconds of the window per window caps are counted over
+    pub cap_window: Option<u64>,
+    pub cap_overflow: Option<CapOverflow>,
+    pub address_change_requires_accept: Option<bool>,
+    pub weight_voting: Option<WeightVoting>,
+    /// CW4 group replacing the whitelist, empty string switches back to the whitelist
+    pub whitelist_group: Option<String>,
+    /// Denoms accepted by `Deposit`, empty list accepts any denom
+    pub deposit_denoms: Option<Vec<String>>,
+    /// Number of protocol payouts paid out by `ContinueDistribution` at once,
+    /// zero pays out everything in `Distribute`
+    pub distribution_batch_size: Option<u32>,
+    /// Denom everything is swapped to before splitting, empty string disables swapping
+    pub payout_denom: Option<String>,
+    /// DEX pairs used instead of the Terra market to swap given denoms
+    pub swap_pairs: Option<Vec<SwapPair>>,
+    /// Maximal spread accepted when swapping on `swap_pairs`, within [0, 1]
+    pub max_spread: Option<Decimal>,
+    pub burn_strategy: Option<BurnStrategy>,
+    /// Approves pending whitelist application, as `ApproveApplication` does
+    pub approve_application: Option<ApplicationApproval>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ApplicationApproval {
+    pub id: u64,
+    pub weight: Option<Decimal>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -46,6 +397,8 @@ pub enum QueryMsg {
 pub struct Whitelist {
     pub address: String,
     pub protocol: String,
+    /// If set, protocol share is sent there instead of `address`
+    pub destination: Option<Destination>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -54,3 +407,104 @@ pub struct WeightPerProtocol {
     pub protocol: String,
     pub weight: Decimal,
 }
+
+/// Where tokens paid out by the contract end up
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Destination {
+    /// Send tokens to given address
+    Address { address: String },
+    /// Burn tokens with `BankMsg::Burn`
+    NativeBurn {},
+    /// Fund the community pool
+    CommunityPool {},
+    /// Execute `msg` on given contract with tokens attached as funds
+    Contract { address: String, msg: Binary },
+    /// Delegate tokens from the contract to validators, split by weights which
+    /// have to sum up to 1
+    Stake { validators: Vec<(String, Decimal)> },
+    /// Send tokens over IBC to an address on another chain
+    Ibc {
+        channel_id: String,
+        remote_address: String,
+        /// Packet timeout in seconds from the block time of the transfer
+        timeout_seconds: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WeightedBurnSink {
+    pub sink: Destination,
+    pub weight: Decimal,
+}
+
+/// Terraswap compatible pair swapping `denom` to the payout denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SwapPair {
+    pub denom: String,
+    pub pair: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WeightVoting {
+    pub voters: VoterSet,
+    /// Time in seconds proposals are open for voting
+    pub voting_period: u64,
+    /// Part of total voting weight which has to vote yes to pass a proposal
+    pub threshold: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoterSet {
+    /// Fixed list of voters with their voting weights
+    Fixed { voters: Vec<(String, u64)> },
+    /// Members of CW4 group contract
+    Group { contract: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnStrategy {
+    /// Send burn share to the burn sinks, or to `burn_address` if there are none
+    Direct {},
+    /// Swap burn share to `target_denom` on the terraswap compatible `pair` and burn
+    /// what is bought back
+    BuybackAndBurn { pair: String, target_denom: String },
+}
+
+/// Whitelist entries a cap applies to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapTarget {
+    Protocol { protocol: String },
+    Address { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RecipientCap {
+    pub target: CapTarget,
+    pub denom: String,
+    /// Maximum amount paid out by a single distribution
+    pub per_distribution: Option<Uint128>,
+    /// Maximum amount paid out within the cap window
+    pub per_window: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CapUsageResponse {
+    pub caps: Vec<CapUsageEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CapUsageEntry {
+    pub cap: crate::state::RecipientCap,
+    /// Amount paid out within the last `cap_window` seconds
+    pub received: Uint128,
+    /// Amount which can still be paid out right now, `None` if unlimited
+    pub remaining: Option<Uint128>,
+}
@@ -1,4 +1,32 @@
+mod address_change;
+mod applications;
+mod burn_sinks;
 mod burn_the_bottom;
+mod buyback;
+mod caps;
 mod config;
+mod config_history;
+mod cw3_admin;
+mod deposit;
+mod destinations;
+mod dex_swap;
 mod distribute;
+mod epoch;
+mod group;
+mod ibc;
+mod migration;
+mod mock_deps;
+mod mock_pair;
+mod mock_v0_1;
+mod paginated_distribution;
+mod payout_denom;
+mod staking;
+mod sudo;
 mod suite;
+mod sweep;
+mod terra_mock;
+mod thresholds;
+mod timelock;
+mod vesting;
+mod weight_voting;
+mod whitelist;
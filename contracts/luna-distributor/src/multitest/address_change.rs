@@ -0,0 +1,186 @@
+use cosmwasm_std::{to_binary, Addr, Decimal};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, Destination, Whitelist};
+use crate::state::WeightPerProtocol;
+
+#[test]
+fn change_address() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "anchor")])
+        .with_weights_per_protocol(&[("terraswap", 60), ("anchor", 40)])
+        .build();
+
+    suite.change_my_address("contract1", "contract3").unwrap();
+
+    let whitelist = suite.query_whitelist(None, None, None).unwrap();
+    let entries: Vec<_> = whitelist
+        .iter()
+        .map(|entry| (entry.address.as_str(), entry.protocol.as_str()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![("contract2", "anchor"), ("contract3", "terraswap")]
+    );
+    // weights are not touched
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap()[1],
+        WeightPerProtocol {
+            protocol: "terraswap".to_owned(),
+            weight: Decimal::percent(60),
+        }
+    );
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    let revision = revisions.last().unwrap();
+    assert_eq!(revision.action, "change_my_address");
+    assert_eq!(revision.sender, Addr::unchecked("contract1"));
+}
+
+#[test]
+fn only_whitelisted_can_change() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 50)])
+        .build();
+
+    let err = suite.change_my_address("random", "contract3").unwrap_err();
+    assert_eq!(
+        ContractError::NotWhitelisted("random".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .change_my_address("contract1", "contract2")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AlreadyWhitelisted("contract2".to_owned(), "terraswap".to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn two_step_change() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .propose_config_change(
+            owner.as_str(),
+            ConfigChange {
+                address_change_requires_accept: Some(true),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.execute_config_change(owner.as_str()).unwrap();
+
+    suite.change_my_address("contract1", "contract3").unwrap();
+    assert_eq!(
+        suite.query_whitelist(None, None, None).unwrap()[0].address,
+        Addr::unchecked("contract1")
+    );
+    assert_eq!(
+        suite.query_pending_address_change("contract1").unwrap(),
+        Some(Addr::unchecked("contract3"))
+    );
+
+    let err = suite
+        .accept_address_change("random", "contract1")
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite
+        .accept_address_change("contract3", "contract1")
+        .unwrap();
+    assert_eq!(
+        suite.query_whitelist(None, None, None).unwrap()[0].address,
+        Addr::unchecked("contract3")
+    );
+    assert_eq!(
+        suite.query_pending_address_change("contract1").unwrap(),
+        None
+    );
+
+    let err = suite
+        .accept_address_change("contract3", "contract1")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NoPendingAddressChange("contract1".to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn destination_rotated() {
+    let mut suite = SuiteBuilder::new()
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .build();
+
+    let hook = to_binary(&"deposit").unwrap();
+    let owner = suite.owner();
+    suite
+        .update_config(
+            owner.as_str(),
+            None,
+            None,
+            None,
+            vec![Whitelist {
+                address: "contract1".to_owned(),
+                protocol: "terraswap".to_owned(),
+                destination: Some(Destination::Contract {
+                    address: "contract1".to_owned(),
+                    msg: hook.clone(),
+                }),
+            }],
+            None,
+        )
+        .unwrap();
+
+    suite.change_my_address("contract1", "contract3").unwrap();
+
+    assert_eq!(
+        suite.query_whitelist(None, None, None).unwrap()[0].destination,
+        Some(crate::state::Destination::Contract {
+            address: Addr::unchecked("contract3"),
+            msg: hook,
+        })
+    );
+}
+
+#[test]
+fn separate_destination_not_rotated() {
+    let mut suite = SuiteBuilder::new()
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .update_config(
+            owner.as_str(),
+            None,
+            None,
+            None,
+            vec![Whitelist {
+                address: "contract1".to_owned(),
+                protocol: "terraswap".to_owned(),
+                destination: Some(Destination::Address {
+                    address: "treasury".to_owned(),
+                }),
+            }],
+            None,
+        )
+        .unwrap();
+
+    let err = suite
+        .change_my_address("contract1", "contract3")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SeparateDestination("contract1".to_owned(), "treasury".to_owned()),
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,170 @@
+use cosmwasm_std::{Addr, Decimal};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{ApplicationApproval, ConfigChange};
+use crate::state::{ApplicationStatus, WeightPerProtocol, Whitelist};
+
+#[test]
+fn apply_and_approve() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+
+    suite
+        .apply_for_whitelist("applicant", "terraswap", "contract1")
+        .unwrap();
+
+    let applications = suite
+        .query_pending_applications(None, None)
+        .unwrap()
+        .applications;
+    assert_eq!(applications.len(), 1);
+    assert_eq!(applications[0].id, 0);
+    assert_eq!(applications[0].protocol, "terraswap");
+    assert_eq!(applications[0].address, Addr::unchecked("contract1"));
+    assert_eq!(applications[0].applicant, Addr::unchecked("applicant"));
+    assert_eq!(applications[0].status, ApplicationStatus::Pending);
+
+    suite
+        .approve_application(owner.as_str(), 0, Decimal::percent(100))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_whitelist(None, None, None).unwrap(),
+        vec![Whitelist {
+            address: Addr::unchecked("contract1"),
+            protocol: "terraswap".to_owned(),
+            destination: None,
+        }]
+    );
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap(),
+        vec![WeightPerProtocol {
+            protocol: "terraswap".to_owned(),
+            weight: Decimal::percent(100),
+        }]
+    );
+    assert!(suite
+        .query_pending_applications(None, None)
+        .unwrap()
+        .applications
+        .is_empty());
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    assert_eq!(revisions.last().unwrap().action, "approve_application");
+
+    let err = suite
+        .apply_for_whitelist("applicant", "terraswap", "contract1")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AlreadyWhitelisted("contract1".to_owned(), "terraswap".to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn approve_requires_protocol_weight() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+
+    suite
+        .apply_for_whitelist("applicant", "terraswap", "contract1")
+        .unwrap();
+
+    let err = suite
+        .approve_application(owner.as_str(), 0, None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MissingProtocol("terraswap".to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn reject() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+
+    suite
+        .apply_for_whitelist("applicant", "terraswap", "contract1")
+        .unwrap();
+    suite
+        .apply_for_whitelist("applicant", "anchor", "contract2")
+        .unwrap();
+
+    let err = suite.reject_application("applicant", 0).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite.reject_application(owner.as_str(), 0).unwrap();
+
+    let applications = suite
+        .query_pending_applications(None, None)
+        .unwrap()
+        .applications;
+    assert_eq!(applications.len(), 1);
+    assert_eq!(applications[0].id, 1);
+    assert!(suite.query_whitelist(None, None, None).unwrap().is_empty());
+
+    let err = suite
+        .approve_application(owner.as_str(), 0, Decimal::percent(50))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ApplicationNotPending(0),
+        err.downcast().unwrap()
+    );
+
+    let err = suite.reject_application(owner.as_str(), 5).unwrap_err();
+    assert_eq!(ContractError::NoSuchApplication(5), err.downcast().unwrap());
+}
+
+#[test]
+fn approve_through_timelock() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(3600).build();
+    let owner = suite.owner();
+
+    suite
+        .apply_for_whitelist("applicant", "terraswap", "contract1")
+        .unwrap();
+    suite
+        .approve_application(owner.as_str(), 0, Decimal::percent(100))
+        .unwrap();
+
+    // approval is queued as config change
+    let pending = suite.query_pending_config_change().unwrap().unwrap();
+    assert_eq!(
+        pending.change,
+        ConfigChange {
+            approve_application: Some(ApplicationApproval {
+                id: 0,
+                weight: Some(Decimal::percent(100)),
+            }),
+            ..ConfigChange::default()
+        }
+    );
+    assert!(suite.query_whitelist(None, None, None).unwrap().is_empty());
+    assert_eq!(
+        suite
+            .query_pending_applications(None, None)
+            .unwrap()
+            .applications
+            .len(),
+        1
+    );
+
+    suite.advance_time(3600);
+    suite.execute_config_change(owner.as_str()).unwrap();
+
+    assert_eq!(
+        suite.query_whitelist(None, None, None).unwrap(),
+        vec![Whitelist {
+            address: Addr::unchecked("contract1"),
+            protocol: "terraswap".to_owned(),
+            destination: None,
+        }]
+    );
+    assert!(suite
+        .query_pending_applications(None, None)
+        .unwrap()
+        .applications
+        .is_empty());
+}
@@ -0,0 +1,176 @@
+use cosmwasm_std::{coin, Addr, Binary, CosmosMsg, Decimal};
+
+use super::suite::SuiteBuilder;
+use crate::community_pool::fund_community_pool_msg;
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, Destination, WeightedBurnSink};
+
+#[test]
+fn update_burn_sinks() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                burn_sinks: Some(vec![
+                    WeightedBurnSink {
+                        sink: Destination::Address {
+                            address: "burnaddress".to_owned(),
+                        },
+                        weight: Decimal::percent(60),
+                    },
+                    WeightedBurnSink {
+                        sink: Destination::CommunityPool {},
+                        weight: Decimal::percent(30),
+                    },
+                    WeightedBurnSink {
+                        sink: Destination::NativeBurn {},
+                        weight: Decimal::percent(10),
+                    },
+                ]),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_config().unwrap().burn_sinks,
+        vec![
+            crate::state::WeightedBurnSink {
+                sink: crate::state::Destination::Address {
+                    address: Addr::unchecked("burnaddress"),
+                },
+                weight: Decimal::percent(60),
+            },
+            crate::state::WeightedBurnSink {
+                sink: crate::state::Destination::CommunityPool {},
+                weight: Decimal::percent(30),
+            },
+            crate::state::WeightedBurnSink {
+                sink: crate::state::Destination::NativeBurn {},
+                weight: Decimal::percent(10),
+            },
+        ]
+    );
+}
+
+#[test]
+fn invalid_weights() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    let err = suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                burn_sinks: Some(vec![
+                    WeightedBurnSink {
+                        sink: Destination::NativeBurn {},
+                        weight: Decimal::percent(50),
+                    },
+                    WeightedBurnSink {
+                        sink: Destination::CommunityPool {},
+                        weight: Decimal::percent(40),
+                    },
+                ]),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidBurnSinkWeights {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn community_pool_msg_encoding() {
+    let msg = fund_community_pool_msg(
+        &Addr::unchecked("terra1jv65s3grqf6v6jl3dp4t6c9t9rk99cd8pm7utl"),
+        coin(1_000_000, "uluna"),
+    );
+
+    // MsgFundCommunityPool{amount: [1000000uluna], depositor: "terra1jv65...7utl"}
+    assert_eq!(
+        msg,
+        CosmosMsg::Stargate {
+            type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_owned(),
+            value: Binary::from_base64(
+                "ChAKBXVsdW5hEgcxMDAwMDAwEix0ZXJyYTFqdjY1czNncnFmNnY2amwzZHA0dDZjOXQ5cms5OWNkOHBtN3V0bA=="
+            )
+            .unwrap(),
+        }
+    );
+}
+
+#[test]
+fn burn_the_bottom_with_sinks() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_funds(user, &[coin(1_000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                burn_sinks: Some(vec![
+                    WeightedBurnSink {
+                        sink: Destination::Address {
+                            address: "burnaddress".to_owned(),
+                        },
+                        weight: Decimal::percent(50),
+                    },
+                    WeightedBurnSink {
+                        sink: Destination::NativeBurn {},
+                        weight: Decimal::percent(50),
+                    },
+                ]),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit(user, &[coin(1_000, "uluna")]).unwrap();
+    suite.burn_the_bottom(user, "uluna").unwrap();
+    assert_eq!(suite.query_balance("burnaddress", "uluna").unwrap(), 500);
+    // the other half is burned natively, so nothing is left anywhere
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+}
+
+#[test]
+fn zero_weight_sink_skipped() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_funds(user, &[coin(1_000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                burn_sinks: Some(vec![
+                    WeightedBurnSink {
+                        sink: Destination::Address {
+                            address: "burnaddress".to_owned(),
+                        },
+                        weight: Decimal::one(),
+                    },
+                    WeightedBurnSink {
+                        sink: Destination::CommunityPool {},
+                        weight: Decimal::zero(),
+                    },
+                ]),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit(user, &[coin(1_000, "uluna")]).unwrap();
+    // zero community pool share is not sent at all - multitest fails on any stargate message
+    suite.burn_the_bottom(user, "uluna").unwrap();
+    assert_eq!(suite.query_balance("burnaddress", "uluna").unwrap(), 1_000);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+}
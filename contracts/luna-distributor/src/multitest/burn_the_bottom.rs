@@ -1,4 +1,4 @@
-use cosmwasm_std::coin;
+use cosmwasm_std::{coin, Attribute, Decimal};
 
 use super::suite::SuiteBuilder;
 use crate::error::ContractError;
@@ -30,9 +30,6 @@ fn balance_not_enough() {
 }
 
 #[test]
-#[should_panic]
-// TODO: Because of tax contract perform TerraQuerier::query_tax_rate which is not implemented in multitest
-// To make test working again, mock implementation is required
 fn balance_less_then() {
     let user = "user";
     let mut suite = SuiteBuilder::new()
@@ -55,9 +52,6 @@ fn balance_less_then() {
 }
 
 #[test]
-#[should_panic]
-// TODO: Because of tax contract perform TerraQuerier::query_tax_rate which is not implemented in multitest
-// To make test working again, mock implementation is required
 fn balance_equal() {
     let user = "user";
     let mut suite = SuiteBuilder::new()
@@ -78,3 +72,27 @@ fn balance_equal() {
     // nothing changed
     assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
 }
+
+#[test]
+fn burn_the_bottom_attributes() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_tax(Decimal::percent(1), &[])
+        .with_funds(user, &[coin(700, "uluna")])
+        .build();
+
+    suite.deposit(user, &[coin(700, "uluna")]).unwrap();
+
+    let response = suite.burn_the_bottom(user, "uluna").unwrap();
+    let wasm = response.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    for attr in [
+        Attribute::new("action", "burn_the_bottom"),
+        Attribute::new("denom", "uluna"),
+        Attribute::new("balance", "700"),
+        // 700 / 1.01
+        Attribute::new("burned", "693"),
+        Attribute::new("tax_paid", "7"),
+    ] {
+        assert!(wasm.attributes.contains(&attr), "missing {:?}", attr);
+    }
+}
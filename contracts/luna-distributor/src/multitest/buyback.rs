@@ -0,0 +1,131 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+
+use super::suite::{Suite, SuiteBuilder};
+use crate::error::ContractError;
+use crate::msg::{BurnStrategy, ConfigChange};
+
+/// Buys back `target_denom` on the suite pair
+fn buyback_change(suite: &mut Suite, target_denom: &str) -> ConfigChange {
+    ConfigChange {
+        burn_strategy: Some(BurnStrategy::BuybackAndBurn {
+            pair: suite.pair(),
+            target_denom: target_denom.to_owned(),
+        }),
+        ..ConfigChange::default()
+    }
+}
+
+#[test]
+fn buyback_config() {
+    let mut suite = SuiteBuilder::new()
+        .with_pair("uluna", Decimal::one(), Decimal::zero(), &[])
+        .build();
+    let owner = suite.owner();
+    let change = buyback_change(&mut suite, "uluna");
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+
+    let pair = suite.pair();
+    assert_eq!(
+        suite.query_config().unwrap().burn_strategy,
+        crate::state::BurnStrategy::BuybackAndBurn {
+            pair: Addr::unchecked(pair),
+            target_denom: "uluna".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn target_denom_burned_directly() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_pair("uluna", Decimal::one(), Decimal::zero(), &[])
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let change = buyback_change(&mut suite, "uluna");
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+
+    suite.distribute("anyone", "uluna").unwrap();
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+    // burn share is burned natively instead of being sent to the burn address
+    let burn_address = suite.burn_address();
+    let developer_address = suite.developer_address();
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 0);
+    assert_eq!(
+        suite.query_balance(&developer_address, "uluna").unwrap(),
+        100
+    );
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 200);
+}
+
+#[test]
+fn burn_share_bought_back() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_pair(
+            "uluna",
+            Decimal::percent(50),
+            Decimal::zero(),
+            &[coin(10_000, "uluna")],
+        )
+        .with_exchange_rate("uusd", "uluna", Decimal::percent(50))
+        .with_funds("depositor", &[coin(1000, "uusd")])
+        .build();
+    let owner = suite.owner();
+    let change = buyback_change(&mut suite, "uluna");
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uusd")]).unwrap();
+
+    let response = suite.distribute("anyone", "uusd").unwrap();
+    assert!(response.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "bought_back" && attr.value == "350uluna")));
+    // 700uusd burn share went to the pair, bought back tokens are burned right away
+    let pair = suite.pair();
+    assert_eq!(suite.query_balance(&pair, "uusd").unwrap(), 700);
+    assert_eq!(suite.query_balance(&pair, "uluna").unwrap(), 9650);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+    assert_eq!(suite.query_contract_balance("uusd").unwrap(), 0);
+    let burn_address = suite.burn_address();
+    let developer_address = suite.developer_address();
+    assert_eq!(suite.query_balance(&burn_address, "uusd").unwrap(), 0);
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 0);
+    assert_eq!(
+        suite.query_balance(&developer_address, "uusd").unwrap(),
+        100
+    );
+    assert_eq!(suite.query_balance("contract1", "uusd").unwrap(), 200);
+}
+
+#[test]
+fn other_asset_not_bought_back() {
+    // pair returns ukrw, whatever its response says
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_pair(
+            "ukrw",
+            Decimal::percent(50),
+            Decimal::zero(),
+            &[coin(10_000, "ukrw")],
+        )
+        .with_funds("depositor", &[coin(1000, "uusd")])
+        .build();
+    let owner = suite.owner();
+    let change = buyback_change(&mut suite, "uluna");
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uusd")]).unwrap();
+
+    let err = suite.distribute("anyone", "uusd").unwrap_err();
+    assert_eq!(
+        ContractError::NothingBoughtBack("uluna".to_owned()),
+        err.downcast().unwrap()
+    );
+    // whole distribution is reverted
+    assert_eq!(suite.query_contract_balance("uusd").unwrap(), 1000);
+    assert_eq!(suite.query_contract_balance("ukrw").unwrap(), 0);
+    assert_eq!(suite.query_balance("contract1", "uusd").unwrap(), 0);
+    let pair = suite.pair();
+    assert_eq!(suite.query_balance(&pair, "ukrw").unwrap(), 10_000);
+}
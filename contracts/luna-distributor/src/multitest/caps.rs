@@ -0,0 +1,214 @@
+use cosmwasm_std::{coin, Addr, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{CapTarget, ConfigChange, RecipientCap};
+use crate::state::CapOverflow;
+
+#[test]
+fn update_caps() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                recipient_caps: Some(vec![
+                    RecipientCap {
+                        target: CapTarget::Protocol {
+                            protocol: "terraswap".to_owned(),
+                        },
+                        denom: "uluna".to_owned(),
+                        per_distribution: Some(Uint128::new(100)),
+                        per_window: None,
+                    },
+                    RecipientCap {
+                        target: CapTarget::Address {
+                            address: "contract1".to_owned(),
+                        },
+                        denom: "uluna".to_owned(),
+                        per_distribution: None,
+                        per_window: Some(Uint128::new(500)),
+                    },
+                ]),
+                cap_window: Some(3600),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(config.cap_window, 3600);
+    assert_eq!(config.cap_overflow, CapOverflow::Burn);
+    assert_eq!(
+        config.recipient_caps[1].target,
+        crate::state::CapTarget::Address {
+            address: Addr::unchecked("contract1")
+        }
+    );
+
+    let usage = suite.query_cap_usage("uluna").unwrap();
+    assert_eq!(usage.caps.len(), 2);
+    assert_eq!(usage.caps[0].remaining, None);
+    assert_eq!(usage.caps[1].received, Uint128::zero());
+    assert_eq!(usage.caps[1].remaining, Some(Uint128::new(500)));
+
+    assert!(suite.query_cap_usage("uusd").unwrap().caps.is_empty());
+}
+
+#[test]
+fn per_window_cap_requires_window() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    let err = suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                recipient_caps: Some(vec![RecipientCap {
+                    target: CapTarget::Protocol {
+                        protocol: "terraswap".to_owned(),
+                    },
+                    denom: "uluna".to_owned(),
+                    per_distribution: None,
+                    per_window: Some(Uint128::new(500)),
+                }]),
+                cap_window: None,
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap_err();
+
+    assert_eq!(ContractError::MissingCapWindow {}, err.downcast().unwrap());
+}
+
+#[test]
+fn overflow_is_burned() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "governance")])
+        .with_weights_per_protocol(&[("terraswap", 50), ("governance", 50)])
+        .with_funds("depositor", &[coin(2000, "uluna")])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                recipient_caps: Some(vec![RecipientCap {
+                    target: CapTarget::Protocol {
+                        protocol: "terraswap".to_owned(),
+                    },
+                    denom: "uluna".to_owned(),
+                    per_distribution: None,
+                    per_window: Some(Uint128::new(60)),
+                }]),
+                cap_window: Some(3600),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+
+    let usage = suite.query_cap_usage("uluna").unwrap();
+    assert_eq!(usage.caps[0].received, Uint128::new(60));
+    assert_eq!(usage.caps[0].remaining, Some(Uint128::zero()));
+    let burn_address = suite.burn_address();
+    // 700 burn share and 40 over the terraswap cap
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 740);
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 60);
+    assert_eq!(suite.query_balance("contract2", "uluna").unwrap(), 100);
+}
+
+#[test]
+fn exhausted_cap_not_paid() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "governance")])
+        .with_weights_per_protocol(&[("terraswap", 50), ("governance", 50)])
+        .with_funds("depositor", &[coin(2000, "uluna")])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                recipient_caps: Some(vec![RecipientCap {
+                    target: CapTarget::Protocol {
+                        protocol: "terraswap".to_owned(),
+                    },
+                    denom: "uluna".to_owned(),
+                    per_distribution: None,
+                    per_window: Some(Uint128::new(60)),
+                }]),
+                cap_window: Some(3600),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+
+    // cap is used up within the window, so terraswap gets no transfer at all
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    let response = suite.distribute("anyone", "uluna").unwrap();
+    assert!(!response.events.iter().any(|ev| ev.ty == "transfer"
+        && ev
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "recipient" && attr.value == "contract1")));
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 60);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+}
+
+#[test]
+fn window_is_rolling() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "governance")])
+        .with_weights_per_protocol(&[("terraswap", 50), ("governance", 50)])
+        .with_funds("depositor", &[coin(3000, "uluna")])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                recipient_caps: Some(vec![RecipientCap {
+                    target: CapTarget::Protocol {
+                        protocol: "terraswap".to_owned(),
+                    },
+                    denom: "uluna".to_owned(),
+                    per_distribution: None,
+                    per_window: Some(Uint128::new(60)),
+                }]),
+                cap_window: Some(3600),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 60);
+
+    // a fixed window would have been over here, the rolling one still counts the first payout
+    suite.advance_time(3000);
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 60);
+    let usage = suite.query_cap_usage("uluna").unwrap();
+    assert_eq!(usage.caps[0].remaining, Some(Uint128::zero()));
+
+    // first payout is out of the window now
+    suite.advance_time(600);
+    let usage = suite.query_cap_usage("uluna").unwrap();
+    assert_eq!(usage.caps[0].received, Uint128::zero());
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 120);
+}
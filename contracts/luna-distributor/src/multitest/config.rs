@@ -1,8 +1,8 @@
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{Addr, Attribute, Decimal, Uint128};
 
 use super::suite::SuiteBuilder;
 use crate::error::ContractError;
-use crate::state::{Config, WeightPerProtocol, Whitelist};
+use crate::state::{BurnStrategy, CapOverflow, Config, WeightPerProtocol, Whitelist};
 
 #[test]
 fn query() {
@@ -19,21 +19,21 @@ fn query() {
         Whitelist {
             address: Addr::unchecked("contract1"),
             protocol: "terraswap".to_owned(),
+            destination: None,
         },
         Whitelist {
             address: Addr::unchecked("contract2"),
             protocol: "curve".to_owned(),
+            destination: None,
         },
         Whitelist {
             address: Addr::unchecked("contract3"),
             protocol: "multichain".to_owned(),
+            destination: None,
         },
     ];
+    // weights are stored by protocol name
     let weight_per_protocol = vec![
-        WeightPerProtocol {
-            protocol: "terraswap".to_owned(),
-            weight: Decimal::percent(50),
-        },
         WeightPerProtocol {
             protocol: "curve".to_owned(),
             weight: Decimal::percent(30),
@@ -42,6 +42,10 @@ fn query() {
             protocol: "multichain".to_owned(),
             weight: Decimal::percent(20),
         },
+        WeightPerProtocol {
+            protocol: "terraswap".to_owned(),
+            weight: Decimal::percent(50),
+        },
     ];
 
     let res = suite.query_config().unwrap();
@@ -51,14 +55,36 @@ fn query() {
             admin: suite.owner().to_string(),
             burn_address: Addr::unchecked(suite.burn_address()),
             developer_address: Addr::unchecked(suite.developer_address()),
-            whitelist,
-            weight_per_protocol,
             percent_to_burn: Decimal::percent(70),
             percent_to_developer: Decimal::percent(10),
             percent_to_distribute: Decimal::percent(20),
             less_then_threshold: Uint128::new(1_000_000u128),
+            threshold_denom: "uluna".to_owned(),
+            min_distribution: Uint128::zero(),
+            config_change_delay: 0,
+            epoch_length: 0,
+            developer_vesting: None,
+            developer_destination: None,
+            burn_sinks: vec![],
+            recipient_caps: vec![],
+            cap_window: 0,
+            cap_overflow: CapOverflow::Burn,
+            address_change_requires_accept: false,
+            weight_voting: None,
+            whitelist_group: None,
+            deposit_denoms: vec![],
+            distribution_batch_size: 0,
+            payout_denom: None,
+            swap_pairs: vec![],
+            max_spread: Decimal::percent(1),
+            burn_strategy: BurnStrategy::Direct {},
         }
     );
+    assert_eq!(suite.query_whitelist(None, None, None).unwrap(), whitelist);
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap(),
+        weight_per_protocol
+    );
 }
 
 #[test]
@@ -76,14 +102,17 @@ fn update() {
         crate::msg::Whitelist {
             address: "contract1".to_owned(),
             protocol: "terraswap".to_owned(),
+            destination: None,
         },
         crate::msg::Whitelist {
             address: "contract2".to_owned(),
             protocol: "some_other_protocol".to_owned(),
+            destination: None,
         },
         crate::msg::Whitelist {
             address: "contract33".to_owned(),
             protocol: "multichain".to_owned(),
+            destination: None,
         },
     ];
     let new_weight_per_protocol = vec![
@@ -117,28 +146,32 @@ fn update() {
         Whitelist {
             address: Addr::unchecked("contract1"),
             protocol: "terraswap".to_owned(),
+            destination: None,
         },
         Whitelist {
             address: Addr::unchecked("contract2"),
             protocol: "some_other_protocol".to_owned(),
+            destination: None,
         },
         Whitelist {
             address: Addr::unchecked("contract33"),
             protocol: "multichain".to_owned(),
+            destination: None,
         },
     ];
+    // weights are stored by protocol name
     let weight_per_protocol = vec![
         WeightPerProtocol {
-            protocol: "terraswap".to_owned(),
-            weight: Decimal::percent(60),
+            protocol: "multichain".to_owned(),
+            weight: Decimal::percent(17),
         },
         WeightPerProtocol {
             protocol: "some_other_protocol".to_owned(),
             weight: Decimal::percent(23),
         },
         WeightPerProtocol {
-            protocol: "multichain".to_owned(),
-            weight: Decimal::percent(17),
+            protocol: "terraswap".to_owned(),
+            weight: Decimal::percent(60),
         },
     ];
 
@@ -149,14 +182,36 @@ fn update() {
             admin: suite.owner().to_string(),
             burn_address: Addr::unchecked(suite.burn_address()),
             developer_address: Addr::unchecked(suite.developer_address()),
-            whitelist,
-            weight_per_protocol,
             percent_to_burn: Decimal::percent(70),
             percent_to_developer: Decimal::percent(10),
             percent_to_distribute: Decimal::percent(20),
             less_then_threshold: Uint128::new(1_000_000u128),
+            threshold_denom: "uluna".to_owned(),
+            min_distribution: Uint128::zero(),
+            config_change_delay: 0,
+            epoch_length: 0,
+            developer_vesting: None,
+            developer_destination: None,
+            burn_sinks: vec![],
+            recipient_caps: vec![],
+            cap_window: 0,
+            cap_overflow: CapOverflow::Burn,
+            address_change_requires_accept: false,
+            weight_voting: None,
+            whitelist_group: None,
+            deposit_denoms: vec![],
+            distribution_batch_size: 0,
+            payout_denom: None,
+            swap_pairs: vec![],
+            max_spread: Decimal::percent(1),
+            burn_strategy: BurnStrategy::Direct {},
         }
     );
+    assert_eq!(suite.query_whitelist(None, None, None).unwrap(), whitelist);
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap(),
+        weight_per_protocol
+    );
 }
 
 #[test]
@@ -228,3 +283,17 @@ fn update_to_empty_admin() {
         err.downcast().unwrap()
     );
 }
+
+#[test]
+fn update_emits_action() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    let response = suite
+        .update_config(owner.as_str(), None, None, None, None, None)
+        .unwrap();
+    let wasm = response.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    assert!(wasm
+        .attributes
+        .contains(&Attribute::new("action", "update_config")));
+}
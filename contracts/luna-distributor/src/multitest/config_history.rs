@@ -0,0 +1,146 @@
+use cosmwasm_std::Attribute;
+
+use super::suite::SuiteBuilder;
+use crate::msg::ConfigChange;
+use crate::state::FieldChange;
+
+#[test]
+fn update_is_recorded() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    let response = suite
+        .update_config(
+            owner.as_str(),
+            None,
+            Some("newburnaddress".to_owned()),
+            Some("newdevaddress".to_owned()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let event = response
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-update_config")
+        .unwrap();
+    assert!(event.attributes.contains(&Attribute {
+        key: "burn_address".to_owned(),
+        value: "\"newburnaddress\"".to_owned(),
+    }));
+    assert!(event.attributes.contains(&Attribute {
+        key: "developer_address".to_owned(),
+        value: "\"newdevaddress\"".to_owned(),
+    }));
+
+    let history = suite.query_config_history(None, None).unwrap();
+    assert_eq!(history.revisions.len(), 1);
+    let revision = &history.revisions[0];
+    assert_eq!(revision.id, 0);
+    assert_eq!(revision.sender, owner);
+    assert_eq!(revision.action, "update_config");
+    assert_eq!(
+        revision.changes,
+        vec![
+            FieldChange {
+                field: "burn_address".to_owned(),
+                before: format!("\"{}\"", suite.burn_address()),
+                after: "\"newburnaddress\"".to_owned(),
+            },
+            FieldChange {
+                field: "developer_address".to_owned(),
+                before: format!("\"{}\"", suite.developer_address()),
+                after: "\"newdevaddress\"".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn noop_update_is_not_recorded() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    suite
+        .update_config(owner.as_str(), None, None, None, None, None)
+        .unwrap();
+
+    let history = suite.query_config_history(None, None).unwrap();
+    assert_eq!(history.revisions, vec![]);
+}
+
+#[test]
+fn executed_change_is_recorded() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(60).build();
+
+    let owner = suite.owner();
+    let change = ConfigChange {
+        admin: Some("newadmin".to_owned()),
+        ..ConfigChange::default()
+    };
+    suite.propose_config_change(owner.as_str(), change).unwrap();
+    // proposal alone does not change config
+    assert_eq!(
+        suite.query_config_history(None, None).unwrap().revisions,
+        vec![]
+    );
+
+    suite.advance_time(60);
+    suite.execute_config_change(owner.as_str()).unwrap();
+
+    let history = suite.query_config_history(None, None).unwrap();
+    assert_eq!(history.revisions.len(), 1);
+    assert_eq!(history.revisions[0].action, "execute_config_change");
+    assert_eq!(
+        history.revisions[0].changes,
+        vec![FieldChange {
+            field: "admin".to_owned(),
+            before: "\"owner\"".to_owned(),
+            after: "\"newadmin\"".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn pagination() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    for i in 0..5 {
+        suite
+            .update_config(
+                owner.as_str(),
+                None,
+                Some(format!("burnaddress{}", i)),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+    }
+
+    let history = suite.query_config_history(None, 2).unwrap();
+    assert_eq!(
+        history
+            .revisions
+            .iter()
+            .map(|revision| revision.id)
+            .collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+
+    let history = suite.query_config_history(1, 10).unwrap();
+    assert_eq!(
+        history
+            .revisions
+            .iter()
+            .map(|revision| revision.id)
+            .collect::<Vec<_>>(),
+        vec![2, 3, 4]
+    );
+    assert_eq!(
+        history.revisions[2].changes[0].after,
+        "\"burnaddress4\"".to_owned()
+    );
+}
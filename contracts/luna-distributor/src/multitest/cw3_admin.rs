@@ -0,0 +1,78 @@
+use cosmwasm_std::Addr;
+use cw3::{ThresholdResponse, Vote};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{AdminType, ExecuteMsg};
+
+#[test]
+fn admin_info() {
+    let suite = SuiteBuilder::new().build();
+    let response = suite.query_admin_info().unwrap();
+    assert_eq!(response.admin, Some(Addr::unchecked("owner")));
+    assert_eq!(response.admin_type, AdminType::Address {});
+
+    let suite = SuiteBuilder::new().with_admin("".to_owned()).build();
+    let response = suite.query_admin_info().unwrap();
+    assert_eq!(response.admin, None);
+    assert_eq!(response.admin_type, AdminType::None {});
+
+    let mut suite = SuiteBuilder::new()
+        .with_multisig_admin(&[("voter1", 1), ("voter2", 1), ("voter3", 1)], 2)
+        .build();
+    let response = suite.query_admin_info().unwrap();
+    assert_eq!(response.admin, Some(Addr::unchecked(suite.multisig())));
+    assert_eq!(
+        response.admin_type,
+        AdminType::Cw3 {
+            threshold: ThresholdResponse::AbsoluteCount {
+                weight: 2,
+                total_weight: 3
+            }
+        }
+    );
+}
+
+#[test]
+fn update_config_through_proposal() {
+    let mut suite = SuiteBuilder::new()
+        .with_multisig_admin(&[("voter1", 1), ("voter2", 1), ("voter3", 1)], 2)
+        .build();
+
+    let update = ExecuteMsg::UpdateConfig {
+        admin: None,
+        burn_address: Some("newburnaddress".to_owned()),
+        developer_address: None,
+        whitelist: None,
+        weight_per_protocol: None,
+    };
+
+    // voters cannot update config directly
+    let err = suite
+        .update_config(
+            "voter1",
+            None,
+            "newburnaddress".to_owned(),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite.multisig_propose("voter1", &update).unwrap();
+    // proposal has not passed yet
+    suite.multisig_execute("voter1", 1).unwrap_err();
+
+    suite.multisig_vote("voter2", 1, Vote::Yes).unwrap();
+    suite.multisig_execute("voter3", 1).unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(config.burn_address, Addr::unchecked("newburnaddress"));
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    assert_eq!(
+        revisions.last().unwrap().sender,
+        Addr::unchecked(suite.multisig())
+    );
+}
@@ -0,0 +1,133 @@
+use cosmwasm_std::{coin, Addr, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::ConfigChange;
+use crate::state::Contribution;
+
+fn contribution(
+    depositor: &str,
+    denom: &str,
+    deposited: u128,
+    assigned_to_burn: u128,
+) -> Contribution {
+    Contribution {
+        depositor: Addr::unchecked(depositor),
+        denom: denom.to_owned(),
+        deposited: Uint128::new(deposited),
+        assigned_to_burn: Uint128::new(assigned_to_burn),
+    }
+}
+
+#[test]
+fn contributions_are_recorded() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("depositor", &[coin(1000, "uluna"), coin(500, "uusd")])
+        .build();
+
+    suite
+        .deposit_msg("depositor", &[coin(300, "uluna")])
+        .unwrap();
+    suite
+        .deposit_msg("depositor", &[coin(200, "uluna")])
+        .unwrap();
+    suite
+        .deposit_msg("depositor", &[coin(500, "uusd")])
+        .unwrap();
+
+    assert_eq!(
+        suite.query_contributions("depositor").unwrap(),
+        vec![
+            contribution("depositor", "uluna", 500, 350),
+            contribution("depositor", "uusd", 500, 350)
+        ]
+    );
+    assert_eq!(suite.query_contributions("other").unwrap(), vec![]);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 500);
+}
+
+#[test]
+fn invalid_deposits_are_rejected() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("depositor", &[coin(1000, "uluna"), coin(500, "uusd")])
+        .build();
+    let owner = suite.owner();
+
+    let err = suite.deposit_msg("depositor", &[]).unwrap_err();
+    assert_eq!(ContractError::NoFunds {}, err.downcast().unwrap());
+
+    let err = suite
+        .deposit_msg("depositor", &[coin(100, "uluna"), coin(100, "uusd")])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DepositMoreThenOne {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                deposit_denoms: Some(vec!["uluna".to_owned()]),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let err = suite
+        .deposit_msg("depositor", &[coin(100, "uusd")])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnsupportedDenom("uusd".to_owned()),
+        err.downcast().unwrap()
+    );
+    suite
+        .deposit_msg("depositor", &[coin(100, "uluna")])
+        .unwrap();
+
+    // Rejected deposits are not taken
+    assert_eq!(suite.query_contract_balance("uusd").unwrap(), 0);
+    assert_eq!(
+        suite.query_contributions("depositor").unwrap(),
+        vec![contribution("depositor", "uluna", 100, 70)]
+    );
+}
+
+#[test]
+fn top_contributors() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("alice", &[coin(1000, "uluna"), coin(1000, "uusd")])
+        .with_funds("bob", &[coin(1000, "uluna")])
+        .with_funds("carol", &[coin(1000, "uluna")])
+        .build();
+
+    suite.deposit_msg("alice", &[coin(200, "uluna")]).unwrap();
+    suite.deposit_msg("bob", &[coin(500, "uluna")]).unwrap();
+    suite.deposit_msg("carol", &[coin(300, "uluna")]).unwrap();
+    suite.deposit_msg("alice", &[coin(1000, "uusd")]).unwrap();
+
+    assert_eq!(
+        suite.query_top_contributors("uluna", None).unwrap(),
+        vec![
+            contribution("bob", "uluna", 500, 350),
+            contribution("carol", "uluna", 300, 210),
+            contribution("alice", "uluna", 200, 140),
+        ]
+    );
+
+    // Cumulative deposit moves alice to the top
+    suite.deposit_msg("alice", &[coin(400, "uluna")]).unwrap();
+    assert_eq!(
+        suite.query_top_contributors("uluna", 2).unwrap(),
+        vec![
+            contribution("alice", "uluna", 600, 420),
+            contribution("bob", "uluna", 500, 350),
+        ]
+    );
+
+    assert_eq!(
+        suite.query_top_contributors("uusd", None).unwrap(),
+        vec![contribution("alice", "uusd", 1000, 700)]
+    );
+    assert_eq!(suite.query_top_contributors("ukrw", None).unwrap(), vec![]);
+}
@@ -0,0 +1,127 @@
+use cosmwasm_std::{coin, to_binary, Addr, Decimal};
+
+use super::suite::SuiteBuilder;
+use crate::msg::{ConfigChange, Destination, WeightPerProtocol, Whitelist};
+
+#[test]
+fn whitelist_destinations() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let hook = to_binary(&"deposit").unwrap();
+    let owner = suite.owner();
+    suite
+        .update_config(
+            owner.as_str(),
+            None,
+            None,
+            None,
+            vec![
+                Whitelist {
+                    address: "contract1".to_owned(),
+                    protocol: "terraswap".to_owned(),
+                    destination: Some(Destination::Contract {
+                        address: "staking".to_owned(),
+                        msg: hook.clone(),
+                    }),
+                },
+                Whitelist {
+                    address: "contract2".to_owned(),
+                    protocol: "governance".to_owned(),
+                    destination: Some(Destination::CommunityPool {}),
+                },
+            ],
+            vec![
+                WeightPerProtocol {
+                    protocol: "terraswap".to_owned(),
+                    weight: Decimal::percent(50),
+                },
+                WeightPerProtocol {
+                    protocol: "governance".to_owned(),
+                    weight: Decimal::percent(50),
+                },
+            ],
+        )
+        .unwrap();
+
+    let whitelist = suite.query_whitelist(None, None, None).unwrap();
+    assert_eq!(
+        whitelist[0].payout(),
+        crate::state::Destination::Contract {
+            address: Addr::unchecked("staking"),
+            msg: hook,
+        }
+    );
+    assert_eq!(
+        whitelist[1].payout(),
+        crate::state::Destination::CommunityPool {}
+    );
+}
+
+#[test]
+fn developer_destination() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(
+        config.developer_payout(),
+        crate::state::Destination::Address {
+            address: Addr::unchecked(suite.developer_address()),
+        }
+    );
+
+    let owner = suite.owner();
+    suite
+        .propose_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_destination: Some(Destination::CommunityPool {}),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.execute_config_change(owner.as_str()).unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(
+        config.developer_payout(),
+        crate::state::Destination::CommunityPool {}
+    );
+}
+
+#[test]
+fn distribute_to_native_burn() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_funds(user, &[coin(100_000_000, "uluna")])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .update_config(
+            owner.as_str(),
+            None,
+            None,
+            None,
+            vec![Whitelist {
+                address: "contract1".to_owned(),
+                protocol: "burner".to_owned(),
+                destination: Some(Destination::NativeBurn {}),
+            }],
+            vec![WeightPerProtocol {
+                protocol: "burner".to_owned(),
+                weight: Decimal::one(),
+            }],
+        )
+        .unwrap();
+
+    suite.deposit(user, &[coin(100_000_000, "uluna")]).unwrap();
+    suite.distribute(user, "uluna").unwrap();
+    let burn_address = suite.burn_address();
+    assert_eq!(
+        suite.query_balance(&burn_address, "uluna").unwrap(),
+        70_000_000
+    );
+    // protocol share is burned instead of being sent to the whitelisted address
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 0);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+}
@@ -0,0 +1,140 @@
+use cosmwasm_std::{coin, Decimal};
+
+use super::suite::{Suite, SuiteBuilder};
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, SwapPair};
+
+/// Swaps uusd on the suite pair to uluna before splitting
+fn pair_swap_change(suite: &mut Suite, max_spread: Decimal) -> ConfigChange {
+    ConfigChange {
+        payout_denom: Some("uluna".to_owned()),
+        swap_pairs: Some(vec![SwapPair {
+            denom: "uusd".to_owned(),
+            pair: suite.pair(),
+        }]),
+        max_spread: Some(max_spread),
+        ..ConfigChange::default()
+    }
+}
+
+#[test]
+fn invalid_max_spread() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+
+    let err = suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                max_spread: Some(Decimal::percent(101)),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidMaxSpread {}, err.downcast().unwrap());
+    assert_eq!(
+        suite.query_config().unwrap().max_spread,
+        Decimal::percent(1)
+    );
+}
+
+#[test]
+fn spread_exceeded() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_pair(
+            "uluna",
+            Decimal::percent(50),
+            Decimal::percent(5),
+            &[coin(10_000, "uluna")],
+        )
+        .with_funds("depositor", &[coin(1000, "uusd")])
+        .build();
+    let owner = suite.owner();
+    let change = pair_swap_change(&mut suite, Decimal::percent(2));
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uusd")]).unwrap();
+
+    let err = suite.distribute("anyone", "uusd").unwrap_err();
+    assert_eq!(
+        ContractError::MaxSpreadExceeded(
+            "uusd".to_owned(),
+            Decimal::percent(5),
+            Decimal::percent(2)
+        ),
+        err.downcast().unwrap()
+    );
+    // nothing changed
+    assert_eq!(suite.query_contract_balance("uusd").unwrap(), 1000);
+    let pair = suite.pair();
+    assert_eq!(suite.query_balance(&pair, "uluna").unwrap(), 10_000);
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 0);
+}
+
+#[test]
+fn pool_price_below_oracle_price() {
+    // pool price is fine for the pair itself, but well below the oracle
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_pair(
+            "uluna",
+            Decimal::percent(50),
+            Decimal::percent(1),
+            &[coin(10_000, "uluna")],
+        )
+        .with_exchange_rate("uusd", "uluna", Decimal::percent(55))
+        .with_funds("depositor", &[coin(1000, "uusd")])
+        .build();
+    let owner = suite.owner();
+    let change = pair_swap_change(&mut suite, Decimal::percent(2));
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uusd")]).unwrap();
+
+    let err = suite.distribute("anyone", "uusd").unwrap_err();
+    // 500 uluna from the pool instead of 550 uluna by the oracle
+    assert_eq!(
+        ContractError::OraclePriceDeviation(
+            "uusd".to_owned(),
+            Decimal::from_ratio(50u128, 550u128),
+            Decimal::percent(2)
+        ),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.query_contract_balance("uusd").unwrap(), 1000);
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 0);
+}
+
+#[test]
+fn swapped_on_pair_within_spread() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_pair(
+            "uluna",
+            Decimal::percent(50),
+            Decimal::percent(1),
+            &[coin(10_000, "uluna")],
+        )
+        .with_exchange_rate("uusd", "uluna", Decimal::percent(50))
+        .with_funds("depositor", &[coin(1000, "uusd")])
+        .build();
+    let owner = suite.owner();
+    let change = pair_swap_change(&mut suite, Decimal::percent(2));
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uusd")]).unwrap();
+
+    suite.distribute("anyone", "uusd").unwrap();
+    assert_eq!(suite.query_contract_balance("uusd").unwrap(), 0);
+    // 495 uluna received and split, rounding dust stays on the contract
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 1);
+    let burn_address = suite.burn_address();
+    let developer_address = suite.developer_address();
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 346);
+    assert_eq!(
+        suite.query_balance(&developer_address, "uluna").unwrap(),
+        49
+    );
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 99);
+    let pair = suite.pair();
+    assert_eq!(suite.query_balance(&pair, "uusd").unwrap(), 1000);
+    assert_eq!(suite.query_balance(&pair, "uluna").unwrap(), 9505);
+}
@@ -1,12 +1,21 @@
-use cosmwasm_std::{coin, Attribute, Event};
+use cosmwasm_std::{coin, Attribute, Decimal, Event};
 
-use super::suite::SuiteBuilder;
+use super::suite::{payouts, SuiteBuilder};
 use crate::error::ContractError;
 
+fn payout(protocol: &str, address: &str, amount: &str) -> (String, String, String) {
+    (protocol.to_owned(), address.to_owned(), amount.to_owned())
+}
+
+fn transfer(sender: &str, recipient: &str, amount: &str) -> Event {
+    Event::new("transfer").add_attributes(vec![
+        Attribute::new("recipient", recipient),
+        Attribute::new("sender", sender),
+        Attribute::new("amount", amount),
+    ])
+}
+
 #[test]
-#[should_panic]
-// TODO: Because of tax contract perform TerraQuerier::query_tax_rate which is not implemented in multitest
-// To make test working again, mock implementation is required
 fn distribute_works() {
     let user = "user";
     let mut suite = SuiteBuilder::new()
@@ -21,81 +30,33 @@ fn distribute_works() {
 
     suite.deposit(user, &[coin(100_000_000, "uluna")]).unwrap();
 
-    let response = suite.distribute(user, "uluna").unwrap().events;
-    assert_eq!(
-        response[1],
-        Event::new("transfer").add_attributes(vec![
-            Attribute {
-                key: "recipient".to_owned(),
-                value: suite.burn_address(),
-            },
-            Attribute {
-                key: "sender".to_owned(),
-                value: suite.contract(),
-            },
-            Attribute {
-                key: "amount".to_owned(),
-                value: "77780000uluna".to_owned(), // 77.78% of 100_000_000
-            }
-        ])
-    );
-    assert_eq!(
-        response[2],
-        Event::new("transfer").add_attributes(vec![
-            Attribute {
-                key: "recipient".to_owned(),
-                value: "contract1".to_owned(),
-            },
-            Attribute {
-                key: "sender".to_owned(),
-                value: suite.contract(),
-            },
-            Attribute {
-                key: "amount".to_owned(),
-                value: "11110000uluna".to_owned(), // 22.22% of 100_000_000 times 0.5 weight
-            }
-        ])
-    );
+    let contract = suite.contract();
+    let transfers: Vec<_> = suite
+        .distribute(user, "uluna")
+        .unwrap()
+        .events
+        .into_iter()
+        .filter(|ev| ev.ty == "transfer")
+        .collect();
     assert_eq!(
-        response[3],
-        Event::new("transfer").add_attributes(vec![
-            Attribute {
-                key: "recipient".to_owned(),
-                value: "contract2".to_owned(),
-            },
-            Attribute {
-                key: "sender".to_owned(),
-                value: suite.contract(),
-            },
-            Attribute {
-                key: "amount".to_owned(),
-                value: "6666000uluna".to_owned(), // 22.22% of 100_000_000 times 0.3 weight
-            }
-        ])
-    );
-    assert_eq!(
-        response[4],
-        Event::new("transfer").add_attributes(vec![
-            Attribute {
-                key: "recipient".to_owned(),
-                value: "contract3".to_owned(),
-            },
-            Attribute {
-                key: "sender".to_owned(),
-                value: suite.contract(),
-            },
-            Attribute {
-                key: "amount".to_owned(),
-                value: "4444000uluna".to_owned(), // 22.22% of 100_000_000 times 0.2 weight
-            }
-        ])
+        transfers,
+        vec![
+            // 70% of 100_000_000
+            transfer(&contract, &suite.burn_address(), "70000000uluna"),
+            // 10% of 100_000_000
+            transfer(&contract, &suite.developer_address(), "10000000uluna"),
+            // 20% of 100_000_000 times 0.5 weight
+            transfer(&contract, "contract1", "10000000uluna"),
+            // 20% of 100_000_000 times 0.3 weight
+            transfer(&contract, "contract2", "6000000uluna"),
+            // 20% of 100_000_000 times 0.2 weight
+            transfer(&contract, "contract3", "4000000uluna"),
+        ]
     );
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
 }
 
 #[test]
-#[should_panic]
-// TODO: Because of tax contract perform TerraQuerier::query_tax_rate which is not implemented in multitest
-// To make test working again, mock implementation is required
 fn missing_protocol_weight() {
     let user = "user";
     let mut suite = SuiteBuilder::new()
@@ -116,3 +77,70 @@ fn missing_protocol_weight() {
         err.downcast().unwrap()
     );
 }
+
+#[test]
+fn distribute_attributes() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "curve")])
+        .with_weights_per_protocol(&[("terraswap", 50), ("curve", 50)])
+        .with_tax(Decimal::percent(1), &[("uluna", 500_000)])
+        .with_funds(user, &[coin(100_000_000, "uluna")])
+        .build();
+
+    suite.deposit(user, &[coin(100_000_000, "uluna")]).unwrap();
+
+    let response = suite.distribute(user, "uluna").unwrap();
+    assert_eq!(
+        payouts(&response),
+        vec![
+            payout("terraswap", "contract1", "9900990"),
+            payout("curve", "contract2", "9900990"),
+        ]
+    );
+    let wasm = response.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    for attr in [
+        Attribute::new("action", "distribute"),
+        Attribute::new("denom", "uluna"),
+        Attribute::new("balance", "100000000"),
+        // 1% tax of 70_000_000 is capped at 500_000
+        Attribute::new("burned", "69500000"),
+        // 10_000_000 / 1.01
+        Attribute::new("to_developer", "9900990"),
+        Attribute::new("tax_paid", "797030"),
+    ] {
+        assert!(wasm.attributes.contains(&attr), "missing {:?}", attr);
+    }
+    // tax is paid out of the sent amounts, so it stays on the contract in multitest
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 797_030);
+}
+
+#[test]
+fn payouts_reported_per_address() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[
+            ("contract1", "terraswap"),
+            ("contract2", "terraswap"),
+            ("contract3", "curve"),
+        ])
+        .with_weights_per_protocol(&[("terraswap", 25), ("curve", 50)])
+        .with_funds(user, &[coin(1000, "uluna")])
+        .build();
+
+    suite.deposit(user, &[coin(1000, "uluna")]).unwrap();
+
+    let response = suite.distribute(user, "uluna").unwrap();
+    // addresses sharing a protocol are reported separately
+    assert_eq!(
+        payouts(&response),
+        vec![
+            payout("terraswap", "contract1", "50"),
+            payout("terraswap", "contract2", "50"),
+            payout("curve", "contract3", "100"),
+        ]
+    );
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 50);
+    assert_eq!(suite.query_balance("contract2", "uluna").unwrap(), 50);
+    assert_eq!(suite.query_balance("contract3", "uluna").unwrap(), 100);
+}
@@ -0,0 +1,105 @@
+use cosmwasm_std::{coin, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::ConfigChange;
+
+#[test]
+fn first_distribute_starts_epoch() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds(user, &[coin(1_000_000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                epoch_length: Some(100),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit(user, &[coin(1_000_000, "uluna")]).unwrap();
+    suite.distribute(user, "uluna").unwrap();
+
+    // nothing has been paid out yet, whole balance is locked in the epoch
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 1_000_000);
+    let response = suite.query_epoch("uluna").unwrap();
+    let epoch = response.epoch.unwrap();
+    assert_eq!(epoch.end_height, epoch.start_height + 100);
+    assert_eq!(epoch.amount, Uint128::new(1_000_000));
+    assert_eq!(epoch.released, Uint128::zero());
+    assert_eq!(response.releasable, Uint128::zero());
+
+    let err = suite.distribute(user, "uluna").unwrap_err();
+    assert_eq!(ContractError::NothingUnlocked {}, err.downcast().unwrap());
+
+    suite.advance_blocks(25);
+    assert_eq!(
+        suite.query_epoch("uluna").unwrap().releasable,
+        Uint128::new(250_000)
+    );
+
+    // after the epoch end everything is releasable
+    suite.advance_blocks(100);
+    assert_eq!(
+        suite.query_epoch("uluna").unwrap().releasable,
+        Uint128::new(1_000_000)
+    );
+}
+
+#[test]
+fn epoch_requires_balance() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                epoch_length: Some(100),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let err = suite.distribute(user, "uluna").unwrap_err();
+    assert_eq!(ContractError::NoBalance {}, err.downcast().unwrap());
+    assert_eq!(suite.query_epoch("uluna").unwrap().epoch, None);
+}
+
+#[test]
+fn distribute_unlocked_portion() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds(user, &[coin(1_000_000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                epoch_length: Some(100),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit(user, &[coin(1_000_000, "uluna")]).unwrap();
+    suite.distribute(user, "uluna").unwrap();
+
+    suite.advance_blocks(40);
+    suite.distribute(user, "uluna").unwrap();
+    // 40% of the epoch has been unlocked and paid out
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 600_000);
+    assert_eq!(
+        suite.query_epoch("uluna").unwrap().epoch.unwrap().released,
+        Uint128::new(400_000)
+    );
+}
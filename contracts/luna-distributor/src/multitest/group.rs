@@ -0,0 +1,133 @@
+use cosmwasm_std::{coin, Addr};
+use cw4::Member;
+
+use super::suite::{payouts, SuiteBuilder};
+use crate::error::ContractError;
+use crate::msg::ConfigChange;
+
+fn members(list: &[(&str, u64)]) -> Vec<Member> {
+    list.iter()
+        .map(|(addr, weight)| Member {
+            addr: addr.to_string(),
+            weight: *weight,
+        })
+        .collect()
+}
+
+#[test]
+fn refresh_snapshot() {
+    let mut suite = SuiteBuilder::new()
+        .with_group(&[("member1", 1), ("member2", 3)])
+        .build();
+    let owner = suite.owner();
+    let group = suite.group();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                whitelist_group: Some(group),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let response = suite.query_group_members(None, None).unwrap();
+    assert_eq!(response.snapshot, None);
+    assert!(response.members.is_empty());
+
+    suite.refresh_group_snapshot("anyone").unwrap();
+
+    let response = suite.query_group_members(None, None).unwrap();
+    let snapshot = response.snapshot.unwrap();
+    assert_eq!(snapshot.group, Addr::unchecked(suite.group()));
+    assert_eq!(snapshot.total_weight, 4);
+    assert_eq!(response.members, members(&[("member1", 1), ("member2", 3)]));
+
+    let response = suite
+        .query_group_members("member1".to_owned(), None)
+        .unwrap();
+    assert_eq!(response.members, members(&[("member2", 3)]));
+}
+
+#[test]
+fn hook_updates_snapshot() {
+    let mut suite = SuiteBuilder::new()
+        .with_group(&[("member1", 1), ("member2", 3)])
+        .build();
+    let owner = suite.owner();
+    let group = suite.group();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                whitelist_group: Some(group),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.refresh_group_snapshot("anyone").unwrap();
+
+    suite
+        .update_group_members(&[("member3", 2), ("member1", 5)], &["member2"])
+        .unwrap();
+
+    let response = suite.query_group_members(None, None).unwrap();
+    assert_eq!(response.snapshot.unwrap().total_weight, 7);
+    assert_eq!(response.members, members(&[("member1", 5), ("member3", 2)]));
+}
+
+#[test]
+fn hook_only_from_group() {
+    let mut suite = SuiteBuilder::new().with_group(&[("member1", 1)]).build();
+
+    // group is not set as whitelist source yet
+    let err = suite
+        .update_group_members(&[("member2", 1)], &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = suite.refresh_group_snapshot("anyone").unwrap_err();
+    assert_eq!(ContractError::NoWhitelistGroup {}, err.downcast().unwrap());
+}
+
+#[test]
+fn distribute_to_members() {
+    let mut suite = SuiteBuilder::new()
+        .with_group(&[("member1", 1), ("member2", 3)])
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let group = suite.group();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                whitelist_group: Some(group),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    let response = suite.distribute("anyone", "uluna").unwrap();
+
+    // 200 protocol share split by member weights
+    assert_eq!(
+        payouts(&response),
+        vec![
+            ("member1".to_owned(), "member1".to_owned(), "50".to_owned()),
+            ("member2".to_owned(), "member2".to_owned(), "150".to_owned()),
+        ]
+    );
+    assert_eq!(suite.query_balance("member1", "uluna").unwrap(), 50);
+    assert_eq!(suite.query_balance("member2", "uluna").unwrap(), 150);
+    assert_eq!(
+        suite
+            .query_group_members(None, None)
+            .unwrap()
+            .snapshot
+            .unwrap()
+            .total_weight,
+        4
+    );
+}
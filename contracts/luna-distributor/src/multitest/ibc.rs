@@ -0,0 +1,264 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coin, coins, from_binary, ContractResult, CosmosMsg, Event, IbcMsg, Reply,
+    SubMsgExecutionResponse,
+};
+
+use super::mock_deps::{apply_config_change, distribute, mock_contract, MockDeps, CONTRACT};
+use super::suite::SuiteBuilder;
+use crate::contract::{execute, query, reply, sudo};
+use crate::msg::{
+    ConfigChange, Destination, EscrowedIbcTransfer, ExecuteMsg, IbcEscrowResponse,
+    IbcLifecycleComplete, QueryMsg, SudoMsg,
+};
+use crate::state::IbcTransfer;
+use crate::ContractError;
+
+/// Distributes 1000uluna with developer share sent over IBC, the transfer is accepted
+/// by the transfer module as packet 7 on channel-1
+fn sent_over_ibc() -> MockDeps {
+    let mut deps = mock_contract(1000);
+    apply_config_change(
+        &mut deps,
+        ConfigChange {
+            developer_destination: Some(Destination::Ibc {
+                channel_id: "channel-1".to_owned(),
+                remote_address: "osmo1developer".to_owned(),
+                timeout_seconds: 600,
+            }),
+            ..ConfigChange::default()
+        },
+    );
+
+    let transfers: Vec<_> = distribute(&mut deps)
+        .into_iter()
+        .filter(|msg| matches!(msg, CosmosMsg::Ibc(IbcMsg::Transfer { .. })))
+        .collect();
+    assert_eq!(transfers.len(), 1);
+
+    let event = Event::new("send_packet")
+        .add_attribute("packet_src_channel", "channel-1")
+        .add_attribute("packet_sequence", "7");
+    let response = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![event],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "send_ibc_transfer"));
+    // sent tokens left the contract
+    deps.querier.update_balance(CONTRACT, vec![]);
+    deps
+}
+
+fn ibc_escrow(deps: &MockDeps) -> Vec<EscrowedIbcTransfer> {
+    let response: IbcEscrowResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IbcEscrow {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    response.transfers
+}
+
+#[test]
+fn developer_ibc_destination() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    suite
+        .propose_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_destination: Some(Destination::Ibc {
+                    channel_id: "channel-1".to_owned(),
+                    remote_address: "osmo1developer".to_owned(),
+                    timeout_seconds: 600,
+                }),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.execute_config_change(owner.as_str()).unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(
+        config.developer_payout(),
+        crate::state::Destination::Ibc {
+            channel_id: "channel-1".to_owned(),
+            remote_address: "osmo1developer".to_owned(),
+            timeout_seconds: 600,
+        }
+    );
+}
+
+#[test]
+fn retry_unknown_transfer() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite.retry_ibc_transfer("anyone", 0).unwrap_err();
+    assert_eq!(ContractError::NoSuchIbcTransfer(0), err.downcast().unwrap());
+}
+
+#[test]
+fn escrow_empty_by_default() {
+    let suite = SuiteBuilder::new().build();
+
+    let response = suite.query_ibc_escrow(None, None).unwrap();
+    assert!(response.transfers.is_empty());
+}
+
+#[test]
+fn distribute_over_ibc() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_destination: Some(Destination::Ibc {
+                    channel_id: "channel-1".to_owned(),
+                    remote_address: "osmo1developer".to_owned(),
+                    timeout_seconds: 600,
+                }),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    // multitest doesn't support IBC, so the transfer is rejected on dispatch and escrowed
+    suite.distribute("anyone", "uluna").unwrap();
+
+    let response = suite.query_ibc_escrow(None, None).unwrap();
+    assert_eq!(
+        response.transfers,
+        vec![EscrowedIbcTransfer {
+            id: 0,
+            transfer: IbcTransfer {
+                channel_id: "channel-1".to_owned(),
+                remote_address: "osmo1developer".to_owned(),
+                timeout_seconds: 600,
+                amount: coin(100, "uluna"),
+            },
+        }]
+    );
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 100);
+}
+
+#[test]
+fn timed_out_transfer_escrowed() {
+    let mut deps = sent_over_ibc();
+
+    // transfer module refunds the transfer and reports the timeout
+    deps.querier.update_balance(CONTRACT, coins(100, "uluna"));
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+            channel: "channel-1".to_owned(),
+            sequence: 7,
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ibc_escrow(&deps),
+        vec![EscrowedIbcTransfer {
+            id: 0,
+            transfer: IbcTransfer {
+                channel_id: "channel-1".to_owned(),
+                remote_address: "osmo1developer".to_owned(),
+                timeout_seconds: 600,
+                amount: coin(100, "uluna"),
+            },
+        }]
+    );
+    // refunded tokens are not split again
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Distribute {
+            denom: "uluna".to_owned(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::NoBalance {}, err);
+
+    // but they can be sent again
+    let response = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::RetryIbcTransfer { id: 0 },
+    )
+    .unwrap();
+    assert!(matches!(
+        response.messages[0].msg,
+        CosmosMsg::Ibc(IbcMsg::Transfer { .. })
+    ));
+}
+
+#[test]
+fn failed_transfer_escrowed() {
+    let mut deps = sent_over_ibc();
+
+    deps.querier.update_balance(CONTRACT, coins(100, "uluna"));
+    let response = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel: "channel-1".to_owned(),
+            sequence: 7,
+            ack: "invalid receiver".to_owned(),
+            success: false,
+        }),
+    )
+    .unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "error" && attr.value == "invalid receiver"));
+    assert_eq!(ibc_escrow(&deps).len(), 1);
+}
+
+#[test]
+fn acknowledged_transfer_forgotten() {
+    let mut deps = sent_over_ibc();
+
+    let ack = SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+        channel: "channel-1".to_owned(),
+        sequence: 7,
+        ack: "AQ==".to_owned(),
+        success: true,
+    });
+    sudo(deps.as_mut(), mock_env(), ack.clone()).unwrap();
+    assert_eq!(ibc_escrow(&deps), vec![]);
+
+    let err = sudo(deps.as_mut(), mock_env(), ack).unwrap_err();
+    assert_eq!(
+        ContractError::NoSuchIbcPacket("channel-1".to_owned(), 7),
+        err
+    );
+}
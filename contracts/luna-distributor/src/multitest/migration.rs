@@ -0,0 +1,118 @@
+use cosmwasm_std::{coin, Addr, Attribute, Decimal, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::state::{BurnStrategy, CapOverflow, WeightPerProtocol, Whitelist};
+
+#[test]
+fn migrate_v0_1() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "curve")])
+        .with_weights_per_protocol(&[("terraswap", 60), ("curve", 40)])
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .with_v0_1_layout()
+        .build();
+
+    let owner = suite.owner();
+    let response = suite.migrate(owner.as_str()).unwrap();
+    let wasm = response.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    for attr in [
+        Attribute::new("from_version", "0.1.1"),
+        Attribute::new("migrated_whitelist", "2"),
+    ] {
+        assert!(wasm.attributes.contains(&attr), "missing {:?}", attr);
+    }
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(config.admin, "owner");
+    assert_eq!(config.burn_address, Addr::unchecked(suite.burn_address()));
+    assert_eq!(
+        config.developer_address,
+        Addr::unchecked(suite.developer_address())
+    );
+    assert_eq!(config.percent_to_burn, Decimal::percent(70));
+    assert_eq!(config.less_then_threshold, Uint128::new(1_000_000));
+    assert_eq!(config.threshold_denom, "uluna");
+    assert_eq!(config.min_distribution, Uint128::zero());
+    assert_eq!(config.config_change_delay, 0);
+    assert_eq!(config.developer_vesting, None);
+    assert!(config.burn_sinks.is_empty());
+    assert_eq!(config.cap_overflow, CapOverflow::Burn);
+    assert_eq!(config.payout_denom, None);
+    assert!(config.swap_pairs.is_empty());
+    assert_eq!(config.max_spread, Decimal::percent(1));
+    assert_eq!(config.burn_strategy, BurnStrategy::Direct {});
+
+    assert_eq!(
+        suite.query_whitelist(None, None, None).unwrap(),
+        vec![
+            Whitelist {
+                address: Addr::unchecked("contract1"),
+                protocol: "terraswap".to_owned(),
+                destination: None,
+            },
+            Whitelist {
+                address: Addr::unchecked("contract2"),
+                protocol: "curve".to_owned(),
+                destination: None,
+            },
+        ]
+    );
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap(),
+        vec![
+            WeightPerProtocol {
+                protocol: "curve".to_owned(),
+                weight: Decimal::percent(40),
+            },
+            WeightPerProtocol {
+                protocol: "terraswap".to_owned(),
+                weight: Decimal::percent(60),
+            },
+        ]
+    );
+
+    // migrated contract distributes as usual
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 120);
+    assert_eq!(suite.query_balance("contract2", "uluna").unwrap(), 80);
+}
+
+#[test]
+fn migrate_current_version() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_v0_1_layout()
+        .build();
+
+    let owner = suite.owner();
+    suite.migrate(owner.as_str()).unwrap();
+    let config = suite.query_config().unwrap();
+
+    // config is already in the current layout, so it is left as it is
+    let response = suite.migrate(owner.as_str()).unwrap();
+    let wasm = response.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    assert!(!wasm
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "migrated_whitelist"));
+    assert_eq!(suite.query_config().unwrap(), config);
+}
+
+#[test]
+fn duplicated_v0_1_whitelist_not_migrated() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract1", "curve")])
+        .with_weights_per_protocol(&[("terraswap", 60), ("curve", 40)])
+        .with_v0_1_layout()
+        .build();
+
+    let owner = suite.owner();
+    let err = suite.migrate(owner.as_str()).unwrap_err();
+    assert_eq!(
+        ContractError::AlreadyWhitelisted("contract1".to_owned(), "terraswap".to_owned()),
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,91 @@
+//! Multitest has no staking, distribution or IBC modules, so messages and replies of those
+//! are checked on mocked dependencies instead
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, to_binary, ContractResult, CosmosMsg, Decimal, OwnedDeps, SystemResult, Uint128,
+};
+use terra_cosmwasm::{
+    TaxCapResponse, TaxRateResponse, TerraMsgWrapper, TerraQuery, TerraQueryWrapper,
+};
+
+use crate::contract::{execute, instantiate};
+use crate::msg::{ConfigChange, ExecuteMsg, InstantiateMsg, WeightPerProtocol, Whitelist};
+
+pub const CONTRACT: &str = "cosmos2contract";
+
+pub type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier<TerraQueryWrapper>>;
+
+/// Instantiates the contract with one protocol, no tax and `balance` of uluna
+pub fn mock_contract(balance: u128) -> MockDeps {
+    let querier = MockQuerier::new(&[(CONTRACT, &coins(balance, "uluna"))]).with_custom_handler(
+        |query: &TerraQueryWrapper| {
+            let response = match &query.query_data {
+                TerraQuery::TaxRate {} => to_binary(&TaxRateResponse {
+                    rate: Decimal::zero(),
+                }),
+                TerraQuery::TaxCap { .. } => to_binary(&TaxCapResponse {
+                    cap: Uint128::zero(),
+                }),
+                _ => panic!("Unexpected query {:?}", query),
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        },
+    );
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier,
+    };
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            admin: "owner".to_owned(),
+            burn_address: "burn".to_owned(),
+            developer_address: "developer".to_owned(),
+            whitelist: vec![Whitelist {
+                address: "contract1".to_owned(),
+                protocol: "terraswap".to_owned(),
+                destination: None,
+            }],
+            weight_per_protocol: vec![WeightPerProtocol {
+                protocol: "terraswap".to_owned(),
+                weight: Decimal::one(),
+            }],
+            config_change_delay: 0,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+pub fn apply_config_change(deps: &mut MockDeps, change: ConfigChange) {
+    for msg in [
+        ExecuteMsg::ProposeConfigChange {
+            change: Box::new(change),
+        },
+        ExecuteMsg::ExecuteConfigChange {},
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    }
+}
+
+/// Distributes whole uluna balance, returning dispatched messages
+pub fn distribute(deps: &mut MockDeps) -> Vec<CosmosMsg<TerraMsgWrapper>> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Distribute {
+            denom: "uluna".to_owned(),
+        },
+    )
+    .unwrap()
+    .messages
+    .into_iter()
+    .map(|sub_msg| sub_msg.msg)
+    .collect()
+}
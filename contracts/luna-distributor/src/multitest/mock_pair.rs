@@ -0,0 +1,68 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    coins, to_binary, BankMsg, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Uint128,
+};
+use cw_storage_plus::Item;
+
+use crate::dex::{PairExecuteMsg, PairQueryMsg, SimulationResponse};
+
+/// Pair swapping any offered denom to `ask_denom` for fixed `price`, losing `spread` of it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub ask_denom: String,
+    pub price: Decimal,
+    pub spread: Decimal,
+}
+
+const CONFIG: Item<InstantiateMsg> = Item::new("config");
+
+fn simulate(config: &InstantiateMsg, offer_amount: Uint128) -> SimulationResponse {
+    let expected_return = offer_amount * config.price;
+    let spread_amount = expected_return * config.spread;
+    SimulationResponse {
+        return_amount: expected_return - spread_amount,
+        spread_amount,
+        commission_amount: Uint128::zero(),
+    }
+}
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    CONFIG.save(deps.storage, &msg)?;
+    Ok(Response::new())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: PairExecuteMsg,
+) -> StdResult<Response> {
+    let PairExecuteMsg::Swap { offer_asset, .. } = msg;
+    let config = CONFIG.load(deps.storage)?;
+    let simulation = simulate(&config, offer_asset.amount);
+    Ok(Response::new()
+        .add_attribute("action", "swap")
+        .add_attribute("ask_asset", config.ask_denom.clone())
+        .add_attribute("return_amount", simulation.return_amount)
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(simulation.return_amount.u128(), config.ask_denom),
+        }))
+}
+
+pub fn query(deps: Deps, _env: Env, msg: PairQueryMsg) -> StdResult<Binary> {
+    match msg {
+        PairQueryMsg::Simulation { offer_asset } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&simulate(&config, offer_asset.amount))
+        }
+    }
+}
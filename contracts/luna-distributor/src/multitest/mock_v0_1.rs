@@ -0,0 +1,57 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw2::set_contract_version;
+
+use crate::state::{ConfigV0_1, WeightPerProtocol, WhitelistV0_1, CONFIG_V0_1};
+
+/// Contract storing its config as v0.1.1 of the distributor did, to be migrated from
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub burn_address: String,
+    pub developer_address: String,
+    pub whitelist: Vec<(String, String)>,
+    pub weight_per_protocol: Vec<WeightPerProtocol>,
+}
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = ConfigV0_1 {
+        admin: msg.admin,
+        burn_address: Addr::unchecked(msg.burn_address),
+        developer_address: Addr::unchecked(msg.developer_address),
+        whitelist: msg
+            .whitelist
+            .into_iter()
+            .map(|(address, protocol)| WhitelistV0_1 {
+                address: Addr::unchecked(address),
+                protocol,
+            })
+            .collect(),
+        weight_per_protocol: msg.weight_per_protocol,
+        percent_to_burn: Decimal::percent(70),
+        percent_to_developer: Decimal::percent(10),
+        percent_to_distribute: Decimal::percent(20),
+        less_then_threshold: Uint128::new(1_000_000),
+    };
+    set_contract_version(deps.storage, "crates.io:luna-distributor", "0.1.1")?;
+    CONFIG_V0_1.save(deps.storage, &config)?;
+    Ok(Response::new())
+}
+
+pub fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Err(StdError::generic_err("Not supported"))
+}
+
+pub fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&CONFIG_V0_1.load(deps.storage)?)
+}
@@ -0,0 +1,131 @@
+use cosmwasm_std::{coin, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::ConfigChange;
+use crate::state::PendingDistribution;
+
+const RECIPIENTS: &[(&str, &str, u64)] = &[
+    ("terraswapcontract", "terraswap", 50),
+    ("curvecontract", "curve", 30),
+    ("multichaincontract", "multichain", 20),
+];
+
+fn batched_distribution(batch_size: u32) -> ConfigChange {
+    ConfigChange {
+        distribution_batch_size: Some(batch_size),
+        ..ConfigChange::default()
+    }
+}
+
+#[test]
+fn nothing_to_continue() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite.continue_distribution("anyone", None).unwrap_err();
+    assert_eq!(
+        ContractError::NoDistributionInProgress {},
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.query_pending_distribution().unwrap(), None);
+}
+
+#[test]
+fn distribution_in_batches() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(RECIPIENTS)
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let change = batched_distribution(2);
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+
+    // Burn and developer shares are paid out right away, protocol payouts are queued
+    suite.distribute("anyone", "uluna").unwrap();
+    let burn_address = suite.burn_address();
+    let developer_address = suite.developer_address();
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 700);
+    assert_eq!(
+        suite.query_balance(&developer_address, "uluna").unwrap(),
+        100
+    );
+    assert_eq!(
+        suite.query_pending_distribution().unwrap(),
+        Some(PendingDistribution {
+            denom: "uluna".to_owned(),
+            total: 3,
+            next: 0,
+            remaining: Uint128::new(200),
+        })
+    );
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 200);
+    assert_eq!(
+        suite.query_balance("terraswapcontract", "uluna").unwrap(),
+        0
+    );
+
+    // No new snapshot until the current one is paid out
+    let err = suite.distribute("anyone", "uluna").unwrap_err();
+    assert_eq!(
+        ContractError::DistributionInProgress("uluna".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    suite.continue_distribution("anyone", None).unwrap();
+    assert_eq!(
+        suite.query_pending_distribution().unwrap(),
+        Some(PendingDistribution {
+            denom: "uluna".to_owned(),
+            total: 3,
+            next: 2,
+            remaining: Uint128::new(40),
+        })
+    );
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 40);
+    assert_eq!(
+        suite.query_balance("terraswapcontract", "uluna").unwrap(),
+        100
+    );
+    assert_eq!(suite.query_balance("curvecontract", "uluna").unwrap(), 60);
+
+    suite.continue_distribution("anyone", None).unwrap();
+    assert_eq!(suite.query_pending_distribution().unwrap(), None);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+    assert_eq!(
+        suite.query_balance("multichaincontract", "uluna").unwrap(),
+        40
+    );
+
+    let err = suite.continue_distribution("anyone", None).unwrap_err();
+    assert_eq!(
+        ContractError::NoDistributionInProgress {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn explicit_limit_overrides_batch_size() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(RECIPIENTS)
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let change = batched_distribution(1);
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+
+    suite.continue_distribution("anyone", 5).unwrap();
+    assert_eq!(suite.query_pending_distribution().unwrap(), None);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+    assert_eq!(
+        suite.query_balance("terraswapcontract", "uluna").unwrap(),
+        100
+    );
+    assert_eq!(suite.query_balance("curvecontract", "uluna").unwrap(), 60);
+    assert_eq!(
+        suite.query_balance("multichaincontract", "uluna").unwrap(),
+        40
+    );
+}
@@ -0,0 +1,116 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+
+use super::suite::SuiteBuilder;
+use crate::msg::{ConfigChange, SwapPair};
+
+fn payout_denom_change(payout_denom: &str) -> ConfigChange {
+    ConfigChange {
+        payout_denom: Some(payout_denom.to_owned()),
+        ..ConfigChange::default()
+    }
+}
+
+#[test]
+fn payout_denom_config() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                payout_denom: Some("uusd".to_owned()),
+                swap_pairs: Some(vec![SwapPair {
+                    denom: "ukrw".to_owned(),
+                    pair: "pair".to_owned(),
+                }]),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(config.payout_denom, Some("uusd".to_owned()));
+    assert_eq!(
+        config.swap_pairs,
+        vec![crate::state::SwapPair {
+            denom: "ukrw".to_owned(),
+            pair: Addr::unchecked("pair"),
+        }]
+    );
+
+    // empty string disables swapping
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                payout_denom: Some(String::new()),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.query_config().unwrap().payout_denom, None);
+}
+
+#[test]
+fn payout_denom_is_not_swapped() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let change = payout_denom_change("uluna");
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+
+    let response = suite.distribute("anyone", "uluna").unwrap();
+    let wasm = response.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    assert!(!wasm.attributes.iter().any(|attr| attr.key == "swapped"));
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+    let burn_address = suite.burn_address();
+    let developer_address = suite.developer_address();
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 700);
+    assert_eq!(
+        suite.query_balance(&developer_address, "uluna").unwrap(),
+        100
+    );
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 200);
+}
+
+#[test]
+fn other_denoms_swapped_before_split() {
+    let mut suite = SuiteBuilder::new()
+        .with_recipients(&[("contract1", "terraswap", 100)])
+        .with_funds("depositor", &[coin(1000, "uusd")])
+        .with_exchange_rate("uusd", "uluna", Decimal::percent(50))
+        .build();
+    let owner = suite.owner();
+    let change = payout_denom_change("uluna");
+    suite.apply_config_change(owner.as_str(), change).unwrap();
+    suite.deposit("depositor", &[coin(1000, "uusd")]).unwrap();
+
+    let response = suite.distribute("anyone", "uusd").unwrap();
+    let wasm: Vec<_> = response
+        .events
+        .iter()
+        .filter(|ev| ev.ty == "wasm")
+        .flat_map(|ev| ev.attributes.iter())
+        .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+        .collect();
+    assert!(wasm.contains(&("swapped", "1000uusd")));
+    // Market swap is done at the oracle rate, so 1000uusd buys 500uluna
+    assert!(wasm.contains(&("received", "500")));
+
+    assert_eq!(suite.query_contract_balance("uusd").unwrap(), 0);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+    // swapped tokens are split instead of the deposited ones
+    let burn_address = suite.burn_address();
+    let developer_address = suite.developer_address();
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 350);
+    assert_eq!(
+        suite.query_balance(&developer_address, "uluna").unwrap(),
+        50
+    );
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 100);
+    assert_eq!(suite.query_balance("contract1", "uusd").unwrap(), 0);
+}
@@ -0,0 +1,192 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coin, coins, BankMsg, CosmosMsg, Decimal, DistributionMsg, FullDelegation, StakingMsg,
+    Validator,
+};
+use terra_cosmwasm::TerraMsgWrapper;
+
+use super::mock_deps::{apply_config_change, distribute, mock_contract, CONTRACT};
+use super::suite::SuiteBuilder;
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, Destination, ExecuteMsg};
+
+fn delegate(validator: &str, amount: u128) -> CosmosMsg<TerraMsgWrapper> {
+    StakingMsg::Delegate {
+        validator: validator.to_owned(),
+        amount: coin(amount, "uluna"),
+    }
+    .into()
+}
+
+#[test]
+fn stake_developer_share() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let validators = vec![
+        ("terravaloper1first".to_owned(), Decimal::percent(75)),
+        ("terravaloper1second".to_owned(), Decimal::percent(25)),
+    ];
+    let owner = suite.owner();
+    suite
+        .propose_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_destination: Some(Destination::Stake {
+                    validators: validators.clone(),
+                }),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.execute_config_change(owner.as_str()).unwrap();
+
+    assert_eq!(
+        suite.query_config().unwrap().developer_destination,
+        Some(crate::state::Destination::Stake { validators })
+    );
+}
+
+#[test]
+fn invalid_validator_weights() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    for validators in [
+        vec![],
+        vec![
+            ("terravaloper1first".to_owned(), Decimal::percent(75)),
+            ("terravaloper1second".to_owned(), Decimal::percent(30)),
+        ],
+    ] {
+        let err = suite
+            .propose_config_change(
+                owner.as_str(),
+                ConfigChange {
+                    developer_destination: Some(Destination::Stake { validators }),
+                    ..ConfigChange::default()
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidValidatorWeights {},
+            err.downcast().unwrap()
+        );
+    }
+}
+
+#[test]
+fn developer_share_delegated() {
+    // developer share is 101uluna
+    let mut deps = mock_contract(1010);
+    apply_config_change(
+        &mut deps,
+        ConfigChange {
+            developer_destination: Some(Destination::Stake {
+                validators: vec![
+                    ("terravaloper1first".to_owned(), Decimal::percent(33)),
+                    ("terravaloper1second".to_owned(), Decimal::percent(33)),
+                    ("terravaloper1third".to_owned(), Decimal::percent(34)),
+                ],
+            }),
+            ..ConfigChange::default()
+        },
+    );
+
+    let delegations: Vec<_> = distribute(&mut deps)
+        .into_iter()
+        .filter(|msg| matches!(msg, CosmosMsg::Staking(_)))
+        .collect();
+    assert_eq!(
+        delegations,
+        vec![
+            // 33% of 101
+            delegate("terravaloper1first", 33),
+            delegate("terravaloper1second", 33),
+            // last validator takes the rest instead of 34% of 101
+            delegate("terravaloper1third", 35),
+        ]
+    );
+}
+
+#[test]
+fn claim_staking_rewards() {
+    let mut deps = mock_contract(0);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ClaimStakingRewards {},
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::NoDelegations {}, err);
+
+    let validator = |address: &str| Validator {
+        address: address.to_owned(),
+        commission: Decimal::percent(5),
+        max_commission: Decimal::percent(10),
+        max_change_rate: Decimal::percent(1),
+    };
+    let delegation = |validator: &str, amount: u128, rewards: u128| FullDelegation {
+        delegator: cosmwasm_std::Addr::unchecked(CONTRACT),
+        validator: validator.to_owned(),
+        amount: coin(amount, "uluna"),
+        can_redelegate: coin(amount, "uluna"),
+        accumulated_rewards: coins(rewards, "uluna"),
+    };
+    deps.querier.update_staking(
+        "uluna",
+        &[
+            validator("terravaloper1first"),
+            validator("terravaloper1second"),
+        ],
+        &[
+            delegation("terravaloper1first", 3000, 60),
+            delegation("terravaloper1second", 1000, 40),
+        ],
+    );
+
+    let response = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ClaimStakingRewards {},
+    )
+    .unwrap();
+    let msgs: Vec<_> = response
+        .messages
+        .into_iter()
+        .map(|sub_msg| sub_msg.msg)
+        .collect();
+    assert_eq!(
+        msgs,
+        vec![
+            CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+                validator: "terravaloper1first".to_owned(),
+            }),
+            CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+                validator: "terravaloper1second".to_owned(),
+            }),
+        ]
+    );
+
+    // withdrawn rewards land on the contract balance and are distributed as usual
+    deps.querier.update_balance(CONTRACT, coins(100, "uluna"));
+    let msgs = distribute(&mut deps);
+    let send = |to_address: &str, amount: u128| -> CosmosMsg<TerraMsgWrapper> {
+        BankMsg::Send {
+            to_address: to_address.to_owned(),
+            amount: coins(amount, "uluna"),
+        }
+        .into()
+    };
+    assert_eq!(
+        msgs,
+        vec![
+            send("burn", 70),
+            send("developer", 10),
+            send("contract1", 20)
+        ]
+    );
+}
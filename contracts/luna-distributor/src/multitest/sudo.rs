@@ -0,0 +1,113 @@
+use cosmwasm_std::{coin, Addr, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, SudoMsg};
+
+#[test]
+fn pause_stops_payouts() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    assert!(!suite.query_paused().unwrap());
+
+    suite.sudo(&SudoMsg::Pause { paused: true }).unwrap();
+    assert!(suite.query_paused().unwrap());
+
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    let err = suite.distribute("anyone", "uluna").unwrap_err();
+    assert_eq!(ContractError::Paused {}, err.downcast().unwrap());
+    let err = suite.burn_the_bottom("anyone", "uluna").unwrap_err();
+    assert_eq!(ContractError::Paused {}, err.downcast().unwrap());
+
+    // config can still be updated
+    let owner = suite.owner();
+    suite
+        .update_config(owner.as_str(), None, "newburn".to_owned(), None, None, None)
+        .unwrap();
+
+    // admin cannot resume contract paused by governance
+    suite.set_paused(owner.as_str(), false).unwrap();
+    assert!(suite.query_paused().unwrap());
+    let err = suite.distribute("anyone", "uluna").unwrap_err();
+    assert_eq!(ContractError::Paused {}, err.downcast().unwrap());
+
+    suite.sudo(&SudoMsg::Pause { paused: false }).unwrap();
+    assert!(!suite.query_paused().unwrap());
+}
+
+#[test]
+fn force_update_config() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(3600).build();
+
+    suite
+        .sudo(&SudoMsg::ForceUpdateConfig {
+            change: Box::new(ConfigChange {
+                burn_address: Some("newburn".to_owned()),
+                epoch_length: Some(100),
+                ..ConfigChange::default()
+            }),
+        })
+        .unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(config.burn_address, Addr::unchecked("newburn"));
+    assert_eq!(config.epoch_length, 100);
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    let revision = revisions.last().unwrap();
+    assert_eq!(revision.action, "sudo_force_update_config");
+    assert_eq!(revision.sender, Addr::unchecked(suite.contract()));
+}
+
+#[test]
+fn set_admin() {
+    let mut suite = SuiteBuilder::new().with_admin("".to_owned()).build();
+
+    suite
+        .sudo(&SudoMsg::SetAdmin {
+            admin: "newadmin".to_owned(),
+        })
+        .unwrap();
+    assert_eq!(suite.query_config().unwrap().admin, "newadmin");
+
+    // contract is updatable again
+    suite
+        .update_config("newadmin", None, "newburn".to_owned(), None, None, None)
+        .unwrap();
+}
+
+#[test]
+fn sweep_nothing() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .sudo(&SudoMsg::Sweep {
+            denom: "uluna".to_owned(),
+            amount: None,
+            recipient: "treasury".to_owned(),
+        })
+        .unwrap_err();
+    assert_eq!(ContractError::NoBalance {}, err.downcast().unwrap());
+}
+
+#[test]
+fn sweep() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+
+    suite
+        .sudo(&SudoMsg::Sweep {
+            denom: "uluna".to_owned(),
+            amount: Some(Uint128::new(400)),
+            recipient: "treasury".to_owned(),
+        })
+        .unwrap();
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 600);
+    assert_eq!(suite.query_balance("treasury", "uluna").unwrap(), 400);
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    assert_eq!(revisions.last().unwrap().action, "sudo_sweep");
+}
@@ -2,24 +2,116 @@ use anyhow::Result as AnyResult;
 use schemars::JsonSchema;
 use std::fmt;
 
-use cosmwasm_std::{Addr, BalanceResponse, BankQuery, Coin, Decimal, QueryRequest};
-use terra_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    to_binary, Addr, BalanceResponse, BankQuery, Coin, Decimal, QueryRequest, Timestamp, Uint128,
+    WasmMsg,
+};
+use cw0::Duration;
+use cw4::Member;
+use terra_cosmwasm::{TerraMsgWrapper, TerraQueryWrapper};
+use terra_multi_test::{
+    App, AppBuilder, AppResponse, BankKeeper, Contract, ContractWrapper, Executor, WasmKeeper,
+};
 
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, WeightPerProtocol, Whitelist};
-use crate::state::Config;
+use super::terra_mock::TerraMock;
+use crate::msg::{
+    AdminInfoResponse, ApplicationsResponse, CapUsageResponse, ConfigChange, ConfigHistoryResponse,
+    ContributionsResponse, DeveloperVestingResponse, EpochResponse, ExecuteMsg,
+    GroupMembersResponse, IbcEscrowResponse, InstantiateMsg, MigrateMsg, PausedResponse, QueryMsg,
+    SudoMsg, WeightPerProtocol, WeightPerProtocolResponse, WeightProposalResponse,
+    WeightProposalsResponse, Whitelist, WhitelistResponse,
+};
+use crate::state::{Ballot, Config, Contribution, PendingConfigChange, PendingDistribution, Vote};
 
-pub fn contract_distributor<C>() -> Box<dyn Contract<C>>
+pub fn contract_distributor() -> Box<dyn Contract<TerraMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply)
+    .with_sudo(crate::contract::sudo)
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+pub fn contract_v0_1<C>() -> Box<dyn Contract<C>>
 where
     C: Clone + fmt::Debug + PartialEq + JsonSchema + 'static,
 {
     let contract = ContractWrapper::new_with_empty(
-        crate::contract::execute,
-        crate::contract::instantiate,
-        crate::contract::query,
+        super::mock_v0_1::execute,
+        super::mock_v0_1::instantiate,
+        super::mock_v0_1::query,
+    );
+    Box::new(contract)
+}
+
+pub fn contract_multisig<C>() -> Box<dyn Contract<C>>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema + 'static,
+{
+    let contract = ContractWrapper::new_with_empty(
+        cw3_fixed_multisig::contract::execute,
+        cw3_fixed_multisig::contract::instantiate,
+        cw3_fixed_multisig::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn contract_group<C>() -> Box<dyn Contract<C>>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema + 'static,
+{
+    let contract = ContractWrapper::new_with_empty(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn contract_pair<C>() -> Box<dyn Contract<C>>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema + 'static,
+{
+    let contract = ContractWrapper::new_with_empty(
+        super::mock_pair::execute,
+        super::mock_pair::instantiate,
+        super::mock_pair::query,
     );
     Box::new(contract)
 }
 
+/// Multitest app with Terra specific queries mocked
+pub type TerraApp = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    TerraMock,
+    WasmKeeper<TerraMsgWrapper, TerraQueryWrapper>,
+>;
+
+/// Lists `(protocol, address, amount)` of protocol payouts reported in `response`
+pub fn payouts(response: &AppResponse) -> Vec<(String, String, String)> {
+    response
+        .events
+        .iter()
+        .filter(|ev| ev.ty == "wasm-payout")
+        .map(|ev| {
+            let attr = |key: &str| {
+                ev.attributes
+                    .iter()
+                    .find(|attr| attr.key == key)
+                    .map(|attr| attr.value.clone())
+                    .unwrap_or_default()
+            };
+            (attr("protocol"), attr("address"), attr("amount"))
+        })
+        .collect()
+}
+
 /// Builder for test suite
 #[derive(Debug)]
 pub struct SuiteBuilder {
@@ -28,7 +120,18 @@ pub struct SuiteBuilder {
     pub developer_address: String,
     pub whitelist: Vec<Whitelist>,
     pub weight_per_protocol: Vec<WeightPerProtocol>,
+    pub config_change_delay: u64,
+    /// Members of the CW4 group instantiated with the suite, if any
+    pub group_members: Option<Vec<Member>>,
+    /// Voters and required weight of CW3 multisig set as admin, if any
+    pub multisig: Option<(Vec<cw3_fixed_multisig::msg::Voter>, u64)>,
+    /// Mock DEX pair instantiated with the suite together with its funds, if any
+    pub pair: Option<(super::mock_pair::InstantiateMsg, Vec<Coin>)>,
+    /// Tax rates and oracle prices returned by Terra queries
+    pub terra: TerraMock,
     pub funds: Vec<(Addr, Vec<Coin>)>,
+    /// If set, contract is instantiated with v0.1.1 storage layout, to be migrated
+    pub v0_1_layout: bool,
 }
 
 impl SuiteBuilder {
@@ -39,7 +142,13 @@ impl SuiteBuilder {
             developer_address: "devaddress".to_owned(),
             whitelist: vec![],
             weight_per_protocol: vec![],
+            config_change_delay: 0,
+            group_members: None,
+            multisig: None,
+            pair: None,
+            terra: TerraMock::default(),
             funds: vec![],
+            v0_1_layout: false,
         }
     }
 
@@ -54,6 +163,7 @@ impl SuiteBuilder {
             let entry = Whitelist {
                 address: address.to_string(),
                 protocol: protocol.to_string(),
+                destination: None,
             };
             list.push(entry);
         }
@@ -74,6 +184,102 @@ impl SuiteBuilder {
         self
     }
 
+    /// Whitelists plain addresses, each with the weight of its protocol
+    pub fn with_recipients(self, recipients: &[(&str, &str, u64)]) -> Self {
+        let whitelist: Vec<_> = recipients
+            .iter()
+            .map(|(address, protocol, _)| (*address, *protocol))
+            .collect();
+        let weights: Vec<_> = recipients
+            .iter()
+            .map(|(_, protocol, weight)| (*protocol, *weight))
+            .collect();
+        self.with_whitelist(&whitelist)
+            .with_weights_per_protocol(&weights)
+    }
+
+    pub fn with_config_change_delay(mut self, delay: u64) -> Self {
+        self.config_change_delay = delay;
+        self
+    }
+
+    /// Instantiates CW3 fixed multisig with given voters and makes it the admin
+    pub fn with_multisig_admin(mut self, voters: &[(&str, u64)], required_weight: u64) -> Self {
+        let voters = voters
+            .iter()
+            .map(|(addr, weight)| cw3_fixed_multisig::msg::Voter {
+                addr: addr.to_string(),
+                weight: *weight,
+            })
+            .collect();
+        self.multisig = Some((voters, required_weight));
+        self
+    }
+
+    /// Instantiates CW4 group with given members, owned by the suite owner and
+    /// with the distributor registered as a hook
+    pub fn with_group(mut self, members: &[(&str, u64)]) -> Self {
+        self.group_members = Some(
+            members
+                .iter()
+                .map(|(addr, weight)| Member {
+                    addr: addr.to_string(),
+                    weight: *weight,
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets tax rate of native transfers and tax caps of given denoms
+    pub fn with_tax(mut self, rate: Decimal, caps: &[(&str, u128)]) -> Self {
+        self.terra.tax_rate = rate;
+        for (denom, cap) in caps {
+            self.terra
+                .tax_caps
+                .insert(denom.to_string(), Uint128::new(*cap));
+        }
+        self
+    }
+
+    /// Sets oracle price of one `base_denom` in `quote_denom`
+    pub fn with_exchange_rate(
+        mut self,
+        base_denom: &str,
+        quote_denom: &str,
+        rate: Decimal,
+    ) -> Self {
+        self.terra
+            .exchange_rates
+            .insert((base_denom.to_owned(), quote_denom.to_owned()), rate);
+        self
+    }
+
+    /// Instantiates pair swapping to `ask_denom` for `price`, losing `spread` of the return
+    pub fn with_pair(
+        mut self,
+        ask_denom: &str,
+        price: Decimal,
+        spread: Decimal,
+        funds: &[Coin],
+    ) -> Self {
+        self.pair = Some((
+            super::mock_pair::InstantiateMsg {
+                ask_denom: ask_denom.to_owned(),
+                price,
+                spread,
+            },
+            funds.to_vec(),
+        ));
+        self
+    }
+
+    /// Instantiates contract as v0.1.1, which has to be migrated with `Suite::migrate`
+    pub fn with_v0_1_layout(mut self) -> Self {
+        self.v0_1_layout = true;
+        self
+    }
+
     /// Sets initial amount of distributable tokens on address
     pub fn with_funds(mut self, addr: &str, funds: &[Coin]) -> Self {
         self.funds.push((Addr::unchecked(addr), funds.into()));
@@ -82,17 +288,68 @@ impl SuiteBuilder {
 
     #[track_caller]
     pub fn build(self) -> Suite {
-        let mut app: App = AppBuilder::new().build();
+        let mut app: TerraApp = AppBuilder::new()
+            .with_custom(self.terra)
+            .with_wasm::<TerraMock, _>(WasmKeeper::new())
+            .build();
 
         let owner = Addr::unchecked("owner");
 
-        let admin = self.admin;
+        let multisig = self.multisig.map(|(voters, required_weight)| {
+            let multisig_id = app.store_code(contract_multisig());
+            app.instantiate_contract(
+                multisig_id,
+                owner.clone(),
+                &cw3_fixed_multisig::msg::InstantiateMsg {
+                    voters,
+                    required_weight,
+                    max_voting_period: Duration::Time(3600),
+                },
+                &[],
+                "multisig",
+                None,
+            )
+            .unwrap()
+        });
+
+        let admin = match &multisig {
+            Some(multisig) => multisig.to_string(),
+            None => self.admin,
+        };
         let burn_address = self.burn_address;
         let developer_address = self.developer_address;
 
         let distributor_id = app.store_code(contract_distributor());
-        let distributor_contract = app
-            .instantiate_contract(
+        let distributor_contract = if self.v0_1_layout {
+            let v0_1_id = app.store_code(contract_v0_1());
+            app.instantiate_contract(
+                v0_1_id,
+                owner.clone(),
+                &super::mock_v0_1::InstantiateMsg {
+                    admin,
+                    burn_address: burn_address.clone(),
+                    developer_address: developer_address.clone(),
+                    whitelist: self
+                        .whitelist
+                        .into_iter()
+                        .map(|entry| (entry.address, entry.protocol))
+                        .collect(),
+                    weight_per_protocol: self
+                        .weight_per_protocol
+                        .into_iter()
+                        .map(|entry| crate::state::WeightPerProtocol {
+                            protocol: entry.protocol,
+                            weight: entry.weight,
+                        })
+                        .collect(),
+                },
+                &[],
+                "distributor",
+                Some(owner.to_string()),
+            )
+            .unwrap()
+        } else {
+            app.instantiate_contract(
                 distributor_id,
                 owner.clone(),
                 &InstantiateMsg {
@@ -101,12 +358,50 @@ impl SuiteBuilder {
                     developer_address: developer_address.clone(),
                     whitelist: self.whitelist,
                     weight_per_protocol: self.weight_per_protocol,
+                    config_change_delay: self.config_change_delay,
                 },
                 &[],
                 "distributor",
                 None,
             )
+            .unwrap()
+        };
+
+        let group = self.group_members.map(|members| {
+            let group_id = app.store_code(contract_group());
+            let group = app
+                .instantiate_contract(
+                    group_id,
+                    owner.clone(),
+                    &cw4_group::msg::InstantiateMsg {
+                        admin: Some(owner.to_string()),
+                        members,
+                    },
+                    &[],
+                    "group",
+                    None,
+                )
+                .unwrap();
+            app.execute_contract(
+                owner.clone(),
+                group.clone(),
+                &cw4_group::msg::ExecuteMsg::AddHook {
+                    addr: distributor_contract.to_string(),
+                },
+                &[],
+            )
             .unwrap();
+            group
+        });
+
+        let pair = self.pair.map(|(msg, funds)| {
+            let pair_id = app.store_code(contract_pair());
+            let pair = app
+                .instantiate_contract(pair_id, owner.clone(), &msg, &[], "pair", None)
+                .unwrap();
+            app.init_bank_balance(&pair, funds).unwrap();
+            pair
+        });
 
         let funds = self.funds;
         for (addr, coin) in funds {
@@ -117,6 +412,10 @@ impl SuiteBuilder {
             app,
             owner,
             contract: distributor_contract,
+            distributor_id,
+            group,
+            multisig,
+            pair,
             burn_address,
             developer_address,
         }
@@ -126,10 +425,18 @@ impl SuiteBuilder {
 /// Test suite
 pub struct Suite {
     /// The multitest app
-    app: App,
+    app: TerraApp,
     owner: Addr,
     /// Address of Market contract
     contract: Addr,
+    /// Code id of the distributor, to migrate to
+    distributor_id: u64,
+    /// Address of CW4 group contract
+    group: Option<Addr>,
+    /// Address of CW3 multisig admin
+    multisig: Option<Addr>,
+    /// Address of mock DEX pair
+    pair: Option<Addr>,
     /// Address of burn contract
     burn_address: String,
     /// Address of developer contract
@@ -145,6 +452,18 @@ impl Suite {
         self.contract.to_string()
     }
 
+    pub fn multisig(&mut self) -> String {
+        self.multisig.clone().unwrap().to_string()
+    }
+
+    pub fn group(&mut self) -> String {
+        self.group.clone().unwrap().to_string()
+    }
+
+    pub fn pair(&mut self) -> String {
+        self.pair.clone().unwrap().to_string()
+    }
+
     pub fn burn_address(&mut self) -> String {
         self.burn_address.clone()
     }
@@ -158,6 +477,30 @@ impl Suite {
             .send_tokens(Addr::unchecked(sender), self.contract.clone(), funds)
     }
 
+    pub fn deposit_msg(&mut self, sender: &str, funds: &[Coin]) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::Deposit {},
+            funds,
+        )
+    }
+
+    pub fn continue_distribution(
+        &mut self,
+        sender: &str,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ContinueDistribution {
+                limit: limit.into(),
+            },
+            &[],
+        )
+    }
+
     pub fn distribute(&mut self, sender: &str, denom: &str) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(sender),
@@ -203,6 +546,324 @@ impl Suite {
         )
     }
 
+    pub fn propose_config_change(
+        &mut self,
+        sender: &str,
+        change: ConfigChange,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ProposeConfigChange {
+                change: Box::new(change),
+            },
+            &[],
+        )
+    }
+
+    pub fn execute_config_change(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ExecuteConfigChange {},
+            &[],
+        )
+    }
+
+    /// Proposes `change` and executes it right away, requires zero config change delay
+    pub fn apply_config_change(
+        &mut self,
+        sender: &str,
+        change: ConfigChange,
+    ) -> AnyResult<AppResponse> {
+        self.propose_config_change(sender, change)?;
+        self.execute_config_change(sender)
+    }
+
+    pub fn cancel_config_change(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::CancelConfigChange {},
+            &[],
+        )
+    }
+
+    pub fn claim_developer_vested(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ClaimDeveloperVested {},
+            &[],
+        )
+    }
+
+    pub fn retry_ibc_transfer(&mut self, sender: &str, id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::RetryIbcTransfer { id },
+            &[],
+        )
+    }
+
+    pub fn apply_for_whitelist(
+        &mut self,
+        sender: &str,
+        protocol: &str,
+        address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ApplyForWhitelist {
+                protocol: protocol.into(),
+                address: address.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn approve_application(
+        &mut self,
+        sender: &str,
+        id: u64,
+        weight: impl Into<Option<Decimal>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ApproveApplication {
+                id,
+                weight: weight.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn reject_application(&mut self, sender: &str, id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::RejectApplication { id },
+            &[],
+        )
+    }
+
+    pub fn change_my_address(&mut self, sender: &str, new_address: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ChangeMyAddress {
+                new_address: new_address.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn accept_address_change(
+        &mut self,
+        sender: &str,
+        old_address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::AcceptAddressChange {
+                old_address: old_address.into(),
+            },
+            &[],
+        )
+    }
+
+    /// Proposes executing `msg` on the distributor by the multisig admin
+    pub fn multisig_propose(&mut self, sender: &str, msg: &ExecuteMsg) -> AnyResult<AppResponse> {
+        let msgs = vec![WasmMsg::Execute {
+            contract_addr: self.contract.to_string(),
+            msg: to_binary(msg)?,
+            funds: vec![],
+        }
+        .into()];
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.multisig.clone().unwrap(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Propose {
+                title: "Distributor".to_owned(),
+                description: "Change distributor".to_owned(),
+                msgs,
+                latest: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn multisig_vote(
+        &mut self,
+        sender: &str,
+        proposal_id: u64,
+        vote: cw3::Vote,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.multisig.clone().unwrap(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+    }
+
+    pub fn multisig_execute(&mut self, sender: &str, proposal_id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.multisig.clone().unwrap(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+    }
+
+    pub fn update_group_members(
+        &mut self,
+        add: &[(&str, u64)],
+        remove: &[&str],
+    ) -> AnyResult<AppResponse> {
+        let add = add
+            .iter()
+            .map(|(addr, weight)| Member {
+                addr: addr.to_string(),
+                weight: *weight,
+            })
+            .collect();
+        let remove = remove.iter().map(|addr| addr.to_string()).collect();
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.group.clone().unwrap(),
+            &cw4_group::msg::ExecuteMsg::UpdateMembers { remove, add },
+            &[],
+        )
+    }
+
+    pub fn refresh_group_snapshot(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::RefreshGroupSnapshot {},
+            &[],
+        )
+    }
+
+    pub fn propose_weights(
+        &mut self,
+        sender: &str,
+        weights: &[(&str, u64)],
+    ) -> AnyResult<AppResponse> {
+        let weights = weights
+            .iter()
+            .map(|(protocol, weight)| WeightPerProtocol {
+                protocol: protocol.to_string(),
+                weight: Decimal::percent(*weight),
+            })
+            .collect();
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::ProposeWeights { weights },
+            &[],
+        )
+    }
+
+    pub fn vote_weights(
+        &mut self,
+        sender: &str,
+        proposal_id: u64,
+        vote: Vote,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::VoteWeights { proposal_id, vote },
+            &[],
+        )
+    }
+
+    pub fn set_paused(&mut self, sender: &str, paused: bool) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::SetPaused { paused },
+            &[],
+        )
+    }
+
+    pub fn sweep(
+        &mut self,
+        sender: &str,
+        denom: &str,
+        amount: impl Into<Option<u128>>,
+        recipient: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::Sweep {
+                denom: denom.into(),
+                amount: amount.into().map(Uint128::new),
+                recipient: recipient.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn migrate(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute(
+            Addr::unchecked(sender),
+            WasmMsg::Migrate {
+                contract_addr: self.contract.to_string(),
+                new_code_id: self.distributor_id,
+                msg: to_binary(&MigrateMsg {})?,
+            }
+            .into(),
+        )
+    }
+
+    pub fn sudo(&mut self, msg: &SudoMsg) -> AnyResult<AppResponse> {
+        self.app.wasm_sudo(self.contract.clone(), msg)
+    }
+
+    pub fn block_time(&self) -> Timestamp {
+        self.app.block_info().time
+    }
+
+    /// Moves block time forward by given amount of seconds
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += 1;
+        });
+    }
+
+    /// Moves chain forward by given amount of blocks, 5 seconds each
+    pub fn advance_blocks(&mut self, blocks: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(5 * blocks);
+            block.height += blocks;
+        });
+    }
+
+    pub fn query_paused(&self) -> AnyResult<bool> {
+        let response: PausedResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::Paused {})?;
+        Ok(response.paused)
+    }
+
+    pub fn query_admin_info(&self) -> AnyResult<AdminInfoResponse> {
+        let response: AdminInfoResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::AdminInfo {})?;
+        Ok(response)
+    }
+
     pub fn query_config(&self) -> AnyResult<Config> {
         let response: Config = self
             .app
@@ -211,12 +872,214 @@ impl Suite {
         Ok(response)
     }
 
+    pub fn query_pending_config_change(&self) -> AnyResult<Option<PendingConfigChange>> {
+        let response: Option<PendingConfigChange> = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::PendingConfigChange {})?;
+        Ok(response)
+    }
+
+    pub fn query_config_history(
+        &self,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<ConfigHistoryResponse> {
+        let response: ConfigHistoryResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::ConfigHistory {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_epoch(&self, denom: &str) -> AnyResult<EpochResponse> {
+        let response: EpochResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::Epoch {
+                denom: denom.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_developer_vesting(&self) -> AnyResult<DeveloperVestingResponse> {
+        let response: DeveloperVestingResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::DeveloperVesting {})?;
+        Ok(response)
+    }
+
+    pub fn query_group_members(
+        &self,
+        start_after: impl Into<Option<String>>,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<GroupMembersResponse> {
+        let response: GroupMembersResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::GroupMembers {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_weight_proposal(&self, proposal_id: u64) -> AnyResult<WeightProposalResponse> {
+        let response: WeightProposalResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::WeightProposal { proposal_id },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_weight_proposals(
+        &self,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<WeightProposalsResponse> {
+        let response: WeightProposalsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::WeightProposals {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_weight_vote(&self, proposal_id: u64, voter: &str) -> AnyResult<Option<Ballot>> {
+        let response: Option<Ballot> = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::WeightVote {
+                proposal_id,
+                voter: voter.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_pending_address_change(&self, address: &str) -> AnyResult<Option<Addr>> {
+        let response: Option<Addr> = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::PendingAddressChange {
+                address: address.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_pending_applications(
+        &self,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<ApplicationsResponse> {
+        let response: ApplicationsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::PendingApplications {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_cap_usage(&self, denom: &str) -> AnyResult<CapUsageResponse> {
+        let response: CapUsageResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::CapUsage {
+                denom: denom.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_ibc_escrow(
+        &self,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<IbcEscrowResponse> {
+        let response: IbcEscrowResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::IbcEscrow {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response)
+    }
+
+    pub fn query_whitelist(
+        &self,
+        protocol: impl Into<Option<String>>,
+        start_after: impl Into<Option<String>>,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<Vec<crate::state::Whitelist>> {
+        let response: WhitelistResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::Whitelist {
+                protocol: protocol.into(),
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response.whitelist)
+    }
+
+    pub fn query_weight_per_protocol(&self) -> AnyResult<Vec<crate::state::WeightPerProtocol>> {
+        let response: WeightPerProtocolResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::WeightPerProtocol {})?;
+        Ok(response.weight_per_protocol)
+    }
+
+    pub fn query_pending_distribution(&self) -> AnyResult<Option<PendingDistribution>> {
+        let response: Option<PendingDistribution> = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::PendingDistribution {})?;
+        Ok(response)
+    }
+
+    pub fn query_contributions(&self, address: &str) -> AnyResult<Vec<Contribution>> {
+        let response: ContributionsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::Contributions {
+                address: address.into(),
+            },
+        )?;
+        Ok(response.contributions)
+    }
+
+    pub fn query_top_contributors(
+        &self,
+        denom: &str,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<Vec<Contribution>> {
+        let response: ContributionsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::TopContributors {
+                denom: denom.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response.contributions)
+    }
+
     pub fn query_contract_balance(&self, denom: &str) -> AnyResult<u128> {
+        self.query_balance(self.contract.as_str(), denom)
+    }
+
+    pub fn query_balance(&self, address: &str, denom: &str) -> AnyResult<u128> {
         let balance: BalanceResponse =
             self.app
                 .wrap()
                 .query(&QueryRequest::Bank(BankQuery::Balance {
-                    address: self.contract.to_string(),
+                    address: address.into(),
                     denom: denom.into(),
                 }))?;
         Ok(balance.amount.amount.u128())
@@ -0,0 +1,182 @@
+use cosmwasm_std::coin;
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::ConfigChange;
+use crate::state::VestingSchedule;
+
+#[test]
+fn admin_pauses() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+
+    let err = suite.set_paused("random", true).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite.set_paused(owner.as_str(), true).unwrap();
+    assert!(suite.query_paused().unwrap());
+
+    let err = suite.distribute("anyone", "uluna").unwrap_err();
+    assert_eq!(ContractError::Paused {}, err.downcast().unwrap());
+
+    suite.set_paused(owner.as_str(), false).unwrap();
+    assert!(!suite.query_paused().unwrap());
+}
+
+#[test]
+fn sweep_requires_pause() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    let owner = suite.owner();
+
+    let err = suite
+        .sweep(owner.as_str(), "uluna", None, "treasury")
+        .unwrap_err();
+    assert_eq!(ContractError::NotPaused {}, err.downcast().unwrap());
+
+    suite.set_paused(owner.as_str(), true).unwrap();
+    let err = suite
+        .sweep("random", "uluna", None, "treasury")
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = suite
+        .sweep(owner.as_str(), "uusd", None, "treasury")
+        .unwrap_err();
+    assert_eq!(ContractError::NoBalance {}, err.downcast().unwrap());
+}
+
+#[test]
+fn sweep_is_recorded() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    let owner = suite.owner();
+
+    suite.set_paused(owner.as_str(), true).unwrap();
+    suite
+        .sweep(owner.as_str(), "uluna", 400, "treasury")
+        .unwrap();
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 600);
+    assert_eq!(suite.query_balance("treasury", "uluna").unwrap(), 400);
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    let revision = revisions.last().unwrap();
+    assert_eq!(revision.action, "sweep");
+    assert_eq!(revision.changes[0].field, "balance_uluna");
+    assert_eq!(revision.changes[0].before, "\"1000\"");
+    assert_eq!(revision.changes[0].after, "\"600\"");
+    assert_eq!(revision.changes[1].after, "\"treasury\"");
+}
+
+#[test]
+fn reserved_balance_not_swept() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let schedule = VestingSchedule {
+        start: suite.block_time(),
+        cliff: 100,
+        duration: 1000,
+    };
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(schedule),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    // developer share of 100 stays on the contract until it vests
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 100);
+
+    suite.set_paused(owner.as_str(), true).unwrap();
+    let err = suite
+        .sweep(owner.as_str(), "uluna", None, "treasury")
+        .unwrap_err();
+    assert_eq!(ContractError::NoBalance {}, err.downcast().unwrap());
+    let err = suite
+        .sweep(owner.as_str(), "uluna", 50, "treasury")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SweepExceedsAvailable(50, 0),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn sweep_blocked_by_timelock() {
+    let mut suite = SuiteBuilder::new()
+        .with_config_change_delay(3600)
+        .with_funds("depositor", &[coin(1000, "uluna")])
+        .build();
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    let owner = suite.owner();
+
+    suite.set_paused(owner.as_str(), true).unwrap();
+    let err = suite
+        .sweep(owner.as_str(), "uluna", None, "treasury")
+        .unwrap_err();
+    assert_eq!(ContractError::TimelockActive {}, err.downcast().unwrap());
+}
+
+#[test]
+fn open_epoch_not_swept() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds("depositor", &[coin(1500, "uluna")])
+        .build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                epoch_length: Some(100),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    // 1000 is locked in the epoch, 500 deposited later is not
+    suite.deposit("depositor", &[coin(1000, "uluna")]).unwrap();
+    suite.distribute("anyone", "uluna").unwrap();
+    suite.deposit("depositor", &[coin(500, "uluna")]).unwrap();
+
+    suite.set_paused(owner.as_str(), true).unwrap();
+    let err = suite
+        .sweep(owner.as_str(), "uluna", 600, "treasury")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SweepExceedsAvailable(600, 500),
+        err.downcast().unwrap()
+    );
+    suite
+        .sweep(owner.as_str(), "uluna", None, "treasury")
+        .unwrap();
+    assert_eq!(suite.query_balance("treasury", "uluna").unwrap(), 500);
+    suite.set_paused(owner.as_str(), false).unwrap();
+
+    // the epoch is still fully paid out
+    suite.advance_blocks(100);
+    suite.distribute("anyone", "uluna").unwrap();
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+    let burn_address = suite.burn_address();
+    let developer_address = suite.developer_address();
+    assert_eq!(suite.query_balance(&burn_address, "uluna").unwrap(), 700);
+    assert_eq!(
+        suite.query_balance(&developer_address, "uluna").unwrap(),
+        100
+    );
+    assert_eq!(suite.query_balance("contract1", "uluna").unwrap(), 200);
+}
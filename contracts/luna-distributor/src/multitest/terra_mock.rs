@@ -0,0 +1,156 @@
+use anyhow::{bail, Result as AnyResult};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use cosmwasm_std::{
+    coins, to_binary, Addr, Api, BankMsg, Binary, BlockInfo, CustomQuery, Decimal, Empty, Querier,
+    Storage, Uint128,
+};
+use terra_cosmwasm::{
+    ExchangeRateItem, ExchangeRatesResponse, TaxCapResponse, TaxRateResponse, TerraMsg,
+    TerraMsgWrapper, TerraQuery, TerraQueryWrapper,
+};
+use terra_multi_test::{AppResponse, BankSudo, CosmosRouter, Module};
+
+/// Mocks Terra treasury, oracle and market modules, which are not implemented in multitest
+#[derive(Clone, Debug, Default)]
+pub struct TerraMock {
+    /// Tax rate of native transfers
+    pub tax_rate: Decimal,
+    /// Tax caps per denom, not capped if missing
+    pub tax_caps: HashMap<String, Uint128>,
+    /// Price of one base denom in quote denom, keyed by (base, quote)
+    pub exchange_rates: HashMap<(String, String), Decimal>,
+}
+
+impl TerraMock {
+    fn exchange_rate(&self, base_denom: &str, quote_denom: &str) -> Option<Decimal> {
+        if base_denom == quote_denom {
+            return Some(Decimal::one());
+        }
+        self.exchange_rates
+            .get(&(base_denom.to_owned(), quote_denom.to_owned()))
+            .copied()
+    }
+}
+
+impl Module for TerraMock {
+    type ExecT = TerraMsgWrapper;
+    type QueryT = TerraQueryWrapper;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: TerraMsgWrapper,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg.msg_data {
+            // Market swaps are executed at the oracle exchange rate, without spread
+            TerraMsg::Swap {
+                offer_coin,
+                ask_denom,
+            } => {
+                let rate = match self.exchange_rate(&offer_coin.denom, &ask_denom) {
+                    Some(rate) => rate,
+                    None => bail!(
+                        "No exchange rate between {} and {}",
+                        offer_coin.denom,
+                        ask_denom
+                    ),
+                };
+                let ask_amount = offer_coin.amount * rate;
+
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender.clone(),
+                    BankMsg::Burn {
+                        amount: vec![offer_coin],
+                    }
+                    .into(),
+                )?;
+                router.sudo(
+                    api,
+                    storage,
+                    block,
+                    BankSudo::Mint {
+                        to_address: sender.to_string(),
+                        amount: coins(ask_amount.u128(), ask_denom),
+                    }
+                    .into(),
+                )?;
+
+                Ok(AppResponse::default())
+            }
+            msg => bail!("Unsupported Terra message {:?}", msg),
+        }
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("Terra mock has no sudo messages")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: TerraQueryWrapper,
+    ) -> AnyResult<Binary> {
+        match request.query_data {
+            TerraQuery::TaxRate {} => Ok(to_binary(&TaxRateResponse {
+                rate: self.tax_rate,
+            })?),
+            TerraQuery::TaxCap { denom } => Ok(to_binary(&TaxCapResponse {
+                cap: self
+                    .tax_caps
+                    .get(&denom)
+                    .copied()
+                    .unwrap_or_else(|| Uint128::new(u128::MAX)),
+            })?),
+            TerraQuery::ExchangeRates {
+                base_denom,
+                quote_denoms,
+            } => {
+                let exchange_rates = quote_denoms
+                    .into_iter()
+                    .filter_map(|quote_denom| {
+                        self.exchange_rate(&base_denom, &quote_denom)
+                            .map(|exchange_rate| ExchangeRateItem {
+                                quote_denom,
+                                exchange_rate,
+                            })
+                    })
+                    .collect();
+                Ok(to_binary(&ExchangeRatesResponse {
+                    base_denom,
+                    exchange_rates,
+                })?)
+            }
+            query => bail!("Unsupported Terra query {:?}", query),
+        }
+    }
+}
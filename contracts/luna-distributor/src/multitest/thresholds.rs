@@ -0,0 +1,119 @@
+use cosmwasm_std::{coin, Decimal, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::ConfigChange;
+
+#[test]
+fn below_min_distribution() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds(user, &[coin(100_000_000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                threshold_denom: Some("uluna".to_owned()),
+                min_distribution: Some(Uint128::new(1_000_000)),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit(user, &[coin(700, "uluna")]).unwrap();
+
+    let err = suite.distribute(user, "uluna").unwrap_err();
+    assert_eq!(
+        ContractError::BelowMinDistribution(700u128, 1_000_000u128),
+        err.downcast().unwrap()
+    );
+
+    // nothing changed
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 700);
+}
+
+#[test]
+fn thresholds_recorded_in_config() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                threshold_denom: Some("uusd".to_owned()),
+                min_distribution: Some(Uint128::new(5_000_000)),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(config.threshold_denom, "uusd");
+    assert_eq!(config.min_distribution, Uint128::new(5_000_000));
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    let fields: Vec<_> = revisions
+        .last()
+        .unwrap()
+        .changes
+        .iter()
+        .map(|change| change.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["threshold_denom", "min_distribution"]);
+}
+
+#[test]
+fn burn_the_bottom_priced_in_base_denom() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_exchange_rate("uluna", "uusd", Decimal::percent(5000))
+        .with_funds(user, &[coin(100_000_000, "uusd")])
+        .build();
+
+    // 1 LUNC threshold is worth 50 UST
+    suite.deposit(user, &[coin(40_000_000, "uusd")]).unwrap();
+    suite.burn_the_bottom(user, "uusd").unwrap();
+    let burn_address = suite.burn_address();
+    assert_eq!(
+        suite.query_balance(&burn_address, "uusd").unwrap(),
+        40_000_000
+    );
+
+    suite.deposit(user, &[coin(60_000_000, "uusd")]).unwrap();
+    let err = suite.burn_the_bottom(user, "uusd").unwrap_err();
+    assert_eq!(
+        ContractError::BurnTheBottom(60_000_000, 50_000_000),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn burn_the_bottom_of_unquoted_denom() {
+    let user = "user";
+    let denom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_exchange_rate("uluna", "uusd", Decimal::percent(5000))
+        .with_funds(user, &[coin(2_000_000, denom)])
+        .build();
+
+    // no oracle rate, so raw 1 LUNC threshold is used
+    suite.deposit(user, &[coin(500_000, denom)]).unwrap();
+    suite.burn_the_bottom(user, denom).unwrap();
+    let burn_address = suite.burn_address();
+    assert_eq!(suite.query_balance(&burn_address, denom).unwrap(), 500_000);
+
+    suite.deposit(user, &[coin(1_500_000, denom)]).unwrap();
+    let err = suite.burn_the_bottom(user, denom).unwrap_err();
+    assert_eq!(
+        ContractError::BurnTheBottom(1_500_000, 1_000_000),
+        err.downcast().unwrap()
+    );
+}
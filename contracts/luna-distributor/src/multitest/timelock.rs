@@ -0,0 +1,144 @@
+use cosmwasm_std::{from_slice, Addr};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, InstantiateMsg};
+
+#[test]
+fn delay_defaults_to_no_timelock() {
+    // instantiate message from before the timelock was introduced
+    let msg: InstantiateMsg = from_slice(
+        br#"{
+            "admin": "owner",
+            "burn_address": "burn",
+            "developer_address": "developer",
+            "whitelist": [],
+            "weight_per_protocol": []
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(msg.config_change_delay, 0);
+}
+
+#[test]
+fn update_config_blocked() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(3600).build();
+
+    let owner = suite.owner();
+    let err = suite
+        .update_config(
+            owner.as_str(),
+            None,
+            Some("newburnaddress".to_owned()),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::TimelockActive {}, err.downcast().unwrap());
+}
+
+#[test]
+fn propose_and_execute() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(3600).build();
+
+    let owner = suite.owner();
+    let change = ConfigChange {
+        burn_address: Some("newburnaddress".to_owned()),
+        ..ConfigChange::default()
+    };
+    suite
+        .propose_config_change(owner.as_str(), change.clone())
+        .unwrap();
+
+    let pending = suite.query_pending_config_change().unwrap().unwrap();
+    assert_eq!(pending.change, change);
+    assert_eq!(
+        pending.executable_after,
+        pending.proposed_at.plus_seconds(3600)
+    );
+
+    // delay has not passed yet
+    suite.advance_time(3599);
+    let err = suite.execute_config_change(owner.as_str()).unwrap_err();
+    assert_eq!(
+        ContractError::ConfigChangeNotExecutable(pending.executable_after),
+        err.downcast().unwrap()
+    );
+    assert_eq!(
+        suite.query_config().unwrap().burn_address,
+        Addr::unchecked(suite.burn_address())
+    );
+
+    suite.advance_time(1);
+    suite.execute_config_change(owner.as_str()).unwrap();
+    assert_eq!(
+        suite.query_config().unwrap().burn_address,
+        Addr::unchecked("newburnaddress")
+    );
+    assert_eq!(suite.query_pending_config_change().unwrap(), None);
+}
+
+#[test]
+fn propose_twice() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(3600).build();
+
+    let owner = suite.owner();
+    suite
+        .propose_config_change(owner.as_str(), ConfigChange::default())
+        .unwrap();
+    let err = suite
+        .propose_config_change(owner.as_str(), ConfigChange::default())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ConfigChangeAlreadyPending {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn cancel() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(3600).build();
+
+    let owner = suite.owner();
+    let change = ConfigChange {
+        admin: Some("newadmin".to_owned()),
+        ..ConfigChange::default()
+    };
+    suite.propose_config_change(owner.as_str(), change).unwrap();
+    suite.cancel_config_change(owner.as_str()).unwrap();
+    assert_eq!(suite.query_pending_config_change().unwrap(), None);
+
+    suite.advance_time(3600);
+    let err = suite.execute_config_change(owner.as_str()).unwrap_err();
+    assert_eq!(
+        ContractError::NoPendingConfigChange {},
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.query_config().unwrap().admin, owner.to_string());
+}
+
+#[test]
+fn propose_unauthorized() {
+    let mut suite = SuiteBuilder::new().with_config_change_delay(3600).build();
+
+    let err = suite
+        .propose_config_change("someone_else", ConfigChange::default())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+#[test]
+fn change_delay_without_timelock() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    let change = ConfigChange {
+        config_change_delay: Some(86400),
+        ..ConfigChange::default()
+    };
+    // with zero delay change can be executed right away
+    suite.propose_config_change(owner.as_str(), change).unwrap();
+    suite.execute_config_change(owner.as_str()).unwrap();
+    assert_eq!(suite.query_config().unwrap().config_change_delay, 86400);
+}
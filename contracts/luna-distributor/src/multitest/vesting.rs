@@ -0,0 +1,280 @@
+use cosmwasm_std::{coin, Timestamp, Uint128};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, DeveloperVestingBalance};
+use crate::state::VestingSchedule;
+
+#[test]
+fn query_schedule() {
+    let mut suite = SuiteBuilder::new().build();
+    assert_eq!(suite.query_developer_vesting().unwrap().schedule, None);
+
+    let owner = suite.owner();
+    let schedule = VestingSchedule {
+        start: suite.block_time(),
+        cliff: 100,
+        duration: 1000,
+    };
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(schedule.clone()),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let response = suite.query_developer_vesting().unwrap();
+    assert_eq!(response.schedule, Some(schedule.clone()));
+    assert_eq!(response.balances, vec![]);
+    assert_eq!(
+        suite.query_config().unwrap().developer_vesting,
+        Some(schedule)
+    );
+}
+
+#[test]
+fn claim_unauthorized() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+    let schedule = VestingSchedule {
+        start: suite.block_time(),
+        cliff: 100,
+        duration: 1000,
+    };
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(schedule),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let err = suite.claim_developer_vested("someone_else").unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+#[test]
+fn claim_nothing() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner();
+    let schedule = VestingSchedule {
+        start: suite.block_time(),
+        cliff: 100,
+        duration: 1000,
+    };
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(schedule),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let developer = suite.developer_address();
+    let err = suite.claim_developer_vested(&developer).unwrap_err();
+    assert_eq!(ContractError::NothingToClaim {}, err.downcast().unwrap());
+}
+
+#[test]
+fn schedule_math() {
+    let schedule = VestingSchedule {
+        start: Timestamp::from_seconds(1000),
+        cliff: 100,
+        duration: 1000,
+    };
+    let total = Uint128::new(1_000);
+    let at = Timestamp::from_seconds;
+
+    assert_eq!(schedule.vested(total, at(500)), Uint128::zero());
+    // cliff has not passed yet
+    assert_eq!(schedule.vested(total, at(1099)), Uint128::zero());
+    assert_eq!(schedule.vested(total, at(1100)), Uint128::new(100));
+    assert_eq!(schedule.vested(total, at(1500)), Uint128::new(500));
+    assert_eq!(schedule.vested(total, at(2000)), total);
+    assert_eq!(schedule.vested(total, at(5000)), total);
+}
+
+#[test]
+fn developer_share_accrues() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds(user, &[coin(100_000_000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let schedule = VestingSchedule {
+        start: suite.block_time(),
+        cliff: 100,
+        duration: 1000,
+    };
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(schedule),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    suite.deposit(user, &[coin(100_000_000, "uluna")]).unwrap();
+    suite.distribute(user, "uluna").unwrap();
+
+    // developer share stays on the contract
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 10_000_000);
+
+    suite.advance_time(500);
+    assert_eq!(
+        suite.query_developer_vesting().unwrap().balances,
+        vec![DeveloperVestingBalance {
+            denom: "uluna".to_owned(),
+            accrued: Uint128::new(10_000_000),
+            vested: Uint128::new(5_000_000),
+            unvested: Uint128::new(5_000_000),
+            claimed: Uint128::zero(),
+            claimable: Uint128::new(5_000_000),
+        }]
+    );
+
+    let developer = suite.developer_address();
+    suite.claim_developer_vested(&developer).unwrap();
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 5_000_000);
+}
+
+#[test]
+fn schedule_replaced_after_claim() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds(user, &[coin(1000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let start = suite.block_time();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(VestingSchedule {
+                    start,
+                    cliff: 0,
+                    duration: 1000,
+                }),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.deposit(user, &[coin(1000, "uluna")]).unwrap();
+    suite.distribute(user, "uluna").unwrap();
+
+    suite.advance_time(500);
+    let developer = suite.developer_address();
+    suite.claim_developer_vested(&developer).unwrap();
+    assert_eq!(suite.query_balance(&developer, "uluna").unwrap(), 50);
+
+    // twice as long schedule vests only 25 of already claimed 50
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(VestingSchedule {
+                    start,
+                    cliff: 0,
+                    duration: 2000,
+                }),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let err = suite.claim_developer_vested(&developer).unwrap_err();
+    assert_eq!(ContractError::NothingToClaim {}, err.downcast().unwrap());
+    assert_eq!(
+        suite.query_developer_vesting().unwrap().balances,
+        vec![DeveloperVestingBalance {
+            denom: "uluna".to_owned(),
+            accrued: Uint128::new(100),
+            vested: Uint128::new(25),
+            unvested: Uint128::new(75),
+            claimed: Uint128::new(50),
+            claimable: Uint128::zero(),
+        }]
+    );
+
+    // claimable again once the new schedule catches up
+    suite.advance_time(700);
+    suite.claim_developer_vested(&developer).unwrap();
+    assert_eq!(suite.query_balance(&developer, "uluna").unwrap(), 60);
+}
+
+#[test]
+fn disable_vesting() {
+    let user = "user";
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap")])
+        .with_weights_per_protocol(&[("terraswap", 100)])
+        .with_funds(user, &[coin(2000, "uluna")])
+        .build();
+    let owner = suite.owner();
+    let schedule = VestingSchedule {
+        start: suite.block_time(),
+        cliff: 100,
+        duration: 1000,
+    };
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(schedule.clone()),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.deposit(user, &[coin(1000, "uluna")]).unwrap();
+    suite.distribute(user, "uluna").unwrap();
+
+    let err = suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                developer_vesting: Some(schedule),
+                disable_developer_vesting: Some(true),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ConflictingDeveloperVesting {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                disable_developer_vesting: Some(true),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.query_config().unwrap().developer_vesting, None);
+
+    // accrued share is claimable right away
+    let developer = suite.developer_address();
+    suite.claim_developer_vested(&developer).unwrap();
+    assert_eq!(suite.query_balance(&developer, "uluna").unwrap(), 100);
+
+    // and new developer share is sent directly
+    suite.deposit(user, &[coin(1000, "uluna")]).unwrap();
+    suite.distribute(user, "uluna").unwrap();
+    assert_eq!(suite.query_balance(&developer, "uluna").unwrap(), 200);
+    assert_eq!(suite.query_contract_balance("uluna").unwrap(), 0);
+}
@@ -0,0 +1,238 @@
+use cosmwasm_std::Decimal;
+
+use super::suite::{Suite, SuiteBuilder};
+use crate::error::ContractError;
+use crate::msg::{ConfigChange, VoterSet, WeightVoting, Whitelist};
+use crate::state::{Ballot, ProposalStatus, Vote, WeightPerProtocol};
+
+const VOTING_PERIOD: u64 = 3600;
+
+fn suite_with_voters() -> Suite {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "anchor")])
+        .with_weights_per_protocol(&[("terraswap", 50), ("anchor", 50)])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .propose_config_change(
+            owner.as_str(),
+            ConfigChange {
+                weight_voting: Some(WeightVoting {
+                    voters: VoterSet::Fixed {
+                        voters: vec![
+                            ("voter1".to_owned(), 1),
+                            ("voter2".to_owned(), 2),
+                            ("voter3".to_owned(), 3),
+                        ],
+                    },
+                    voting_period: VOTING_PERIOD,
+                    threshold: Decimal::percent(50),
+                }),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+    suite.execute_config_change(owner.as_str()).unwrap();
+    suite
+}
+
+#[test]
+fn proposal_passes_and_applies_weights() {
+    let mut suite = suite_with_voters();
+
+    suite
+        .propose_weights("voter1", &[("terraswap", 70), ("anchor", 30)])
+        .unwrap();
+    let response = suite.query_weight_proposal(0).unwrap();
+    assert_eq!(response.status, ProposalStatus::Open);
+    assert_eq!(response.proposal.yes, 1);
+    assert_eq!(response.proposal.total_weight, 6);
+
+    suite.vote_weights("voter3", 0, Vote::No).unwrap();
+    assert_eq!(
+        suite.query_weight_vote(0, "voter3").unwrap(),
+        Some(Ballot {
+            vote: Vote::No,
+            weight: 3
+        })
+    );
+    assert_eq!(suite.query_weight_vote(0, "voter2").unwrap(), None);
+
+    suite.vote_weights("voter2", 0, Vote::Yes).unwrap();
+    let response = suite.query_weight_proposal(0).unwrap();
+    assert_eq!(response.status, ProposalStatus::Passed);
+    assert_eq!(response.proposal.yes, 3);
+    assert_eq!(response.proposal.no, 3);
+
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap(),
+        vec![
+            WeightPerProtocol {
+                protocol: "anchor".to_owned(),
+                weight: Decimal::percent(30),
+            },
+            WeightPerProtocol {
+                protocol: "terraswap".to_owned(),
+                weight: Decimal::percent(70),
+            },
+        ]
+    );
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    assert_eq!(revisions.last().unwrap().action, "weight_proposal_passed");
+
+    let err = suite.vote_weights("voter3", 0, Vote::Yes).unwrap_err();
+    assert_eq!(
+        ContractError::WeightProposalNotOpen(0),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn proposal_expires() {
+    let mut suite = suite_with_voters();
+
+    suite
+        .propose_weights("voter1", &[("terraswap", 70), ("anchor", 30)])
+        .unwrap();
+    suite.advance_time(VOTING_PERIOD);
+
+    let response = suite.query_weight_proposal(0).unwrap();
+    assert_eq!(response.status, ProposalStatus::Rejected);
+
+    let err = suite.vote_weights("voter3", 0, Vote::Yes).unwrap_err();
+    assert_eq!(
+        ContractError::WeightProposalNotOpen(0),
+        err.downcast().unwrap()
+    );
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap()[1].weight,
+        Decimal::percent(50)
+    );
+}
+
+#[test]
+fn proposal_rejected_when_cannot_pass() {
+    let mut suite = suite_with_voters();
+
+    suite
+        .propose_weights("voter1", &[("terraswap", 70), ("anchor", 30)])
+        .unwrap();
+    suite.vote_weights("voter2", 0, Vote::No).unwrap();
+    suite.vote_weights("voter3", 0, Vote::No).unwrap();
+
+    // 5 of 6 voted no, so yes cannot reach 50% anymore
+    let response = suite.query_weight_proposal(0).unwrap();
+    assert_eq!(response.status, ProposalStatus::Rejected);
+}
+
+#[test]
+fn only_voters_can_vote_once() {
+    let mut suite = suite_with_voters();
+
+    let err = suite
+        .propose_weights("random", &[("terraswap", 100)])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite
+        .propose_weights("voter1", &[("terraswap", 100), ("anchor", 0)])
+        .unwrap();
+    suite
+        .propose_weights("voter2", &[("terraswap", 0), ("anchor", 100)])
+        .unwrap();
+
+    let err = suite.vote_weights("random", 0, Vote::Yes).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = suite.vote_weights("voter1", 0, Vote::No).unwrap_err();
+    assert_eq!(ContractError::AlreadyVoted(0), err.downcast().unwrap());
+
+    let err = suite.vote_weights("voter1", 7, Vote::No).unwrap_err();
+    assert_eq!(
+        ContractError::NoSuchWeightProposal(7),
+        err.downcast().unwrap()
+    );
+
+    let proposals = suite.query_weight_proposals(None, None).unwrap().proposals;
+    assert_eq!(proposals.len(), 2);
+    let proposals = suite.query_weight_proposals(0, None).unwrap().proposals;
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].proposal.id, 1);
+}
+
+#[test]
+fn invalid_weights_rejected() {
+    let mut suite = suite_with_voters();
+
+    let err = suite
+        .propose_weights("voter1", &[("terraswap", 100)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MissingProtocol("anchor".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose_weights("voter1", &[("terraswap", 70), ("anchor", 40)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ProposedWeightsExceedOne {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn proposal_not_applied_after_whitelist_change() {
+    let mut suite = suite_with_voters();
+
+    suite
+        .propose_weights("voter1", &[("terraswap", 70), ("anchor", 30)])
+        .unwrap();
+
+    // protocol not covered by the proposal is whitelisted meanwhile
+    let owner = suite.owner();
+    suite
+        .apply_config_change(
+            owner.as_str(),
+            ConfigChange {
+                whitelist: Some(vec![
+                    Whitelist {
+                        address: "contract1".to_owned(),
+                        protocol: "terraswap".to_owned(),
+                        destination: None,
+                    },
+                    Whitelist {
+                        address: "contract3".to_owned(),
+                        protocol: "mirror".to_owned(),
+                        destination: None,
+                    },
+                ]),
+                ..ConfigChange::default()
+            },
+        )
+        .unwrap();
+
+    let err = suite.vote_weights("voter3", 0, Vote::Yes).unwrap_err();
+    assert_eq!(
+        ContractError::MissingProtocol("mirror".to_owned()),
+        err.downcast().unwrap()
+    );
+    assert_eq!(
+        suite.query_weight_per_protocol().unwrap()[1].weight,
+        Decimal::percent(50)
+    );
+}
+
+#[test]
+fn voting_disabled() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .propose_weights("voter1", &[("terraswap", 100)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::WeightVotingDisabled {},
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,144 @@
+use cosmwasm_std::{Addr, Decimal};
+
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{WeightPerProtocol, Whitelist};
+
+#[test]
+fn query_by_protocol() {
+    let suite = SuiteBuilder::new()
+        .with_whitelist(&[
+            ("contract1", "terraswap"),
+            ("contract2", "curve"),
+            ("contract3", "terraswap"),
+            ("contract4", "terraswap"),
+        ])
+        .with_weights_per_protocol(&[("terraswap", 20), ("curve", 40)])
+        .build();
+
+    let addresses = |whitelist: Vec<crate::state::Whitelist>| -> Vec<Addr> {
+        whitelist.into_iter().map(|entry| entry.address).collect()
+    };
+
+    let whitelist = suite
+        .query_whitelist("terraswap".to_owned(), None, None)
+        .unwrap();
+    assert_eq!(
+        addresses(whitelist),
+        vec![
+            Addr::unchecked("contract1"),
+            Addr::unchecked("contract3"),
+            Addr::unchecked("contract4")
+        ]
+    );
+
+    let whitelist = suite
+        .query_whitelist("terraswap".to_owned(), "contract1".to_owned(), 1)
+        .unwrap();
+    assert_eq!(addresses(whitelist), vec![Addr::unchecked("contract3")]);
+
+    let whitelist = suite
+        .query_whitelist(None, "contract2".to_owned(), None)
+        .unwrap();
+    assert_eq!(
+        addresses(whitelist),
+        vec![Addr::unchecked("contract3"), Addr::unchecked("contract4")]
+    );
+
+    assert_eq!(
+        suite
+            .query_whitelist("anchor".to_owned(), None, None)
+            .unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn address_whitelisted_once() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let owner = suite.owner();
+    let err = suite
+        .update_config(
+            owner.as_str(),
+            None,
+            None,
+            None,
+            vec![
+                Whitelist {
+                    address: "contract1".to_owned(),
+                    protocol: "terraswap".to_owned(),
+                    destination: None,
+                },
+                Whitelist {
+                    address: "contract1".to_owned(),
+                    protocol: "curve".to_owned(),
+                    destination: None,
+                },
+            ],
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AlreadyWhitelisted("contract1".to_owned(), "terraswap".to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn changed_entries_recorded() {
+    let mut suite = SuiteBuilder::new()
+        .with_whitelist(&[("contract1", "terraswap"), ("contract2", "curve")])
+        .with_weights_per_protocol(&[("terraswap", 50), ("curve", 50)])
+        .build();
+
+    let owner = suite.owner();
+    suite
+        .update_config(
+            owner.as_str(),
+            None,
+            None,
+            None,
+            vec![
+                Whitelist {
+                    address: "contract1".to_owned(),
+                    protocol: "terraswap".to_owned(),
+                    destination: None,
+                },
+                Whitelist {
+                    address: "contract3".to_owned(),
+                    protocol: "curve".to_owned(),
+                    destination: None,
+                },
+            ],
+            vec![
+                WeightPerProtocol {
+                    protocol: "terraswap".to_owned(),
+                    weight: Decimal::percent(50),
+                },
+                WeightPerProtocol {
+                    protocol: "curve".to_owned(),
+                    weight: Decimal::percent(40),
+                },
+            ],
+        )
+        .unwrap();
+
+    let revisions = suite.query_config_history(None, None).unwrap().revisions;
+    let fields: Vec<_> = revisions
+        .last()
+        .unwrap()
+        .changes
+        .iter()
+        .map(|change| change.field.as_str())
+        .collect();
+    // unchanged entries are not recorded
+    assert_eq!(
+        fields,
+        vec![
+            "whitelist.contract2",
+            "whitelist.contract3",
+            "weight_per_protocol.curve"
+        ]
+    );
+}
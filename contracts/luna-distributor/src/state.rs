@@ -1,27 +1,269 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::Item;
+use cosmwasm_std::{
+    to_vec, Addr, Binary, Coin, Decimal, Order, StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, U128Key, U64Key};
+
+use crate::msg::ConfigChange;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub admin: String,
     pub burn_address: Addr,
     pub developer_address: Addr,
-    pub whitelist: Vec<Whitelist>,
-    pub weight_per_protocol: Vec<WeightPerProtocol>,
     pub percent_to_burn: Decimal,
     pub percent_to_developer: Decimal,
     pub percent_to_distribute: Decimal,
-    /// Threshold for BurnTheBottom handle
+    /// Threshold for BurnTheBottom handle, in `threshold_denom`
     pub less_then_threshold: Uint128,
+    /// Denom `less_then_threshold` and `min_distribution` are expressed in. For other denoms
+    /// they are converted with oracle exchange rates at execution time.
+    pub threshold_denom: String,
+    /// Minimal amount, in `threshold_denom`, `Distribute` pays out at once. Zero disables the check.
+    pub min_distribution: Uint128,
+    /// Delay in seconds between proposing and executing a config change.
+    /// If zero, `UpdateConfig` applies changes immediately.
+    pub config_change_delay: u64,
+    /// Length of distribution epoch in blocks. If zero, `Distribute` pays out
+    /// the whole balance at once.
+    pub epoch_length: u64,
+    /// If set, developer share accrues on the contract and is released
+    /// according to this schedule instead of being sent right away
+    pub developer_vesting: Option<VestingSchedule>,
+    /// If set, developer share is sent there instead of `developer_address`
+    pub developer_destination: Option<Destination>,
+    /// Weighted destinations of the burn share. If empty, whole burn share
+    /// is sent to `burn_address`.
+    pub burn_sinks: Vec<WeightedBurnSink>,
+    /// Upper bounds of what whitelisted recipients can receive
+    pub recipient_caps: Vec<RecipientCap>,
+    /// Length in seconds of the window `RecipientCap::per_window` is counted over
+    pub cap_window: u64,
+    /// What happens with the part of protocol share exceeding caps
+    pub cap_overflow: CapOverflow,
+    /// If set, address rotated with `ChangeMyAddress` has to accept it before it takes effect
+    pub address_change_requires_accept: bool,
+    /// If set, voters can change `weight_per_protocol` by passing weight proposals
+    pub weight_voting: Option<WeightVoting>,
+    /// If set, members of this CW4 group replace `whitelist`, each receiving part of
+    /// the protocol share proportional to its member weight
+    pub whitelist_group: Option<Addr>,
+    /// Denoms accepted by `Deposit`. If empty, any native denom is accepted.
+    pub deposit_denoms: Vec<String>,
+    /// If non-zero, `Distribute` only queues protocol payouts, which are then paid out by
+    /// `ContinueDistribution` in batches of this size
+    pub distribution_batch_size: u32,
+    /// If set, `Distribute` swaps other denoms to this one and splits the swapped amount
+    pub payout_denom: Option<String>,
+    /// DEX pairs used to swap to `payout_denom`. Denoms without a pair are swapped
+    /// on the Terra market.
+    pub swap_pairs: Vec<SwapPair>,
+    /// Maximal spread accepted when swapping on `swap_pairs`
+    pub max_spread: Decimal,
+    /// How the burn share is burned
+    pub burn_strategy: BurnStrategy,
+}
+
+impl Config {
+    /// Destination of the developer share
+    pub fn developer_payout(&self) -> Destination {
+        self.developer_destination
+            .clone()
+            .unwrap_or_else(|| Destination::Address {
+                address: self.developer_address.clone(),
+            })
+    }
+
+    /// Lists all fields which differ between `self` and `other`
+    pub fn diff(&self, other: &Config) -> StdResult<Vec<FieldChange>> {
+        let mut changes = vec![];
+        diff_field(&mut changes, "admin", &self.admin, &other.admin)?;
+        diff_field(
+            &mut changes,
+            "burn_address",
+            &self.burn_address,
+            &other.burn_address,
+        )?;
+        diff_field(
+            &mut changes,
+            "developer_address",
+            &self.developer_address,
+            &other.developer_address,
+        )?;
+        diff_field(
+            &mut changes,
+            "percent_to_burn",
+            &self.percent_to_burn,
+            &other.percent_to_burn,
+        )?;
+        diff_field(
+            &mut changes,
+            "percent_to_developer",
+            &self.percent_to_developer,
+            &other.percent_to_developer,
+        )?;
+        diff_field(
+            &mut changes,
+            "percent_to_distribute",
+            &self.percent_to_distribute,
+            &other.percent_to_distribute,
+        )?;
+        diff_field(
+            &mut changes,
+            "less_then_threshold",
+            &self.less_then_threshold,
+            &other.less_then_threshold,
+        )?;
+        diff_field(
+            &mut changes,
+            "threshold_denom",
+            &self.threshold_denom,
+            &other.threshold_denom,
+        )?;
+        diff_field(
+            &mut changes,
+            "min_distribution",
+            &self.min_distribution,
+            &other.min_distribution,
+        )?;
+        diff_field(
+            &mut changes,
+            "config_change_delay",
+            &self.config_change_delay,
+            &other.config_change_delay,
+        )?;
+        diff_field(
+            &mut changes,
+            "epoch_length",
+            &self.epoch_length,
+            &other.epoch_length,
+        )?;
+        diff_field(
+            &mut changes,
+            "developer_vesting",
+            &self.developer_vesting,
+            &other.developer_vesting,
+        )?;
+        diff_field(
+            &mut changes,
+            "developer_destination",
+            &self.developer_destination,
+            &other.developer_destination,
+        )?;
+        diff_field(
+            &mut changes,
+            "burn_sinks",
+            &self.burn_sinks,
+            &other.burn_sinks,
+        )?;
+        diff_field(
+            &mut changes,
+            "recipient_caps",
+            &self.recipient_caps,
+            &other.recipient_caps,
+        )?;
+        diff_field(
+            &mut changes,
+            "cap_window",
+            &self.cap_window,
+            &other.cap_window,
+        )?;
+        diff_field(
+            &mut changes,
+            "cap_overflow",
+            &self.cap_overflow,
+            &other.cap_overflow,
+        )?;
+        diff_field(
+            &mut changes,
+            "weight_voting",
+            &self.weight_voting,
+            &other.weight_voting,
+        )?;
+        diff_field(
+            &mut changes,
+            "whitelist_group",
+            &self.whitelist_group,
+            &other.whitelist_group,
+        )?;
+        diff_field(
+            &mut changes,
+            "address_change_requires_accept",
+            &self.address_change_requires_accept,
+            &other.address_change_requires_accept,
+        )?;
+        diff_field(
+            &mut changes,
+            "deposit_denoms",
+            &self.deposit_denoms,
+            &other.deposit_denoms,
+        )?;
+        diff_field(
+            &mut changes,
+            "distribution_batch_size",
+            &self.distribution_batch_size,
+            &other.distribution_batch_size,
+        )?;
+        diff_field(
+            &mut changes,
+            "payout_denom",
+            &self.payout_denom,
+            &other.payout_denom,
+        )?;
+        diff_field(
+            &mut changes,
+            "swap_pairs",
+            &self.swap_pairs,
+            &other.swap_pairs,
+        )?;
+        diff_field(
+            &mut changes,
+            "max_spread",
+            &self.max_spread,
+            &other.max_spread,
+        )?;
+        diff_field(
+            &mut changes,
+            "burn_strategy",
+            &self.burn_strategy,
+            &other.burn_strategy,
+        )?;
+        Ok(changes)
+    }
+}
+
+/// Pushes a `FieldChange` with JSON encoded values if `before` and `after` differ
+fn diff_field<T: Serialize + PartialEq>(
+    changes: &mut Vec<FieldChange>,
+    field: &str,
+    before: &T,
+    after: &T,
+) -> StdResult<()> {
+    if before != after {
+        changes.push(FieldChange::new(field, before, after)?);
+    }
+    Ok(())
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Whitelist {
     pub address: Addr,
     pub protocol: String,
+    /// If set, protocol share is sent there instead of `address`
+    pub destination: Option<Destination>,
+}
+
+impl Whitelist {
+    /// Destination of the protocol share
+    pub fn payout(&self) -> Destination {
+        self.destination
+            .clone()
+            .unwrap_or_else(|| Destination::Address {
+                address: self.address.clone(),
+            })
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -30,4 +272,622 @@ pub struct WeightPerProtocol {
     pub weight: Decimal,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConfigChange {
+    pub change: ConfigChange,
+    /// Block time at which change has been proposed
+    pub proposed_at: Timestamp,
+    /// Change cannot be executed before that time
+    pub executable_after: Timestamp,
+}
+
+/// Where tokens paid out by the contract end up
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Destination {
+    /// Send tokens to given address
+    Address { address: Addr },
+    /// Burn tokens with `BankMsg::Burn`
+    NativeBurn {},
+    /// Fund the community pool
+    CommunityPool {},
+    /// Execute `msg` on given contract with tokens attached as funds
+    Contract { address: Addr, msg: Binary },
+    /// Delegate tokens from the contract to validators, split by weights
+    Stake { validators: Vec<(String, Decimal)> },
+    /// Send tokens over IBC to an address on another chain
+    Ibc {
+        channel_id: String,
+        remote_address: String,
+        /// Packet timeout in seconds from the block time of the transfer
+        timeout_seconds: u64,
+    },
+}
+
+/// Distribution whose protocol payouts are being paid out in batches
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDistribution {
+    pub denom: String,
+    /// Number of queued payouts
+    pub total: u64,
+    /// Id of the next payout to be paid out
+    pub next: u64,
+    /// Amount still to be paid out, reserved on the contract
+    pub remaining: Uint128,
+}
+
+/// Terraswap compatible pair swapping `denom` to the payout denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapPair {
+    pub denom: String,
+    pub pair: Addr,
+}
+
+/// Swap to the payout denom dispatched by `Distribute`, split once it is replied
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSwap {
+    pub offer: Coin,
+    pub ask_denom: String,
+    /// Available balance of `ask_denom` before the swap
+    pub balance_before: Uint128,
+}
+
+/// Buyback of the burn share dispatched by `Distribute`, burned once it is replied
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBuyback {
+    pub target_denom: String,
+    /// Balance of `target_denom` before the swap
+    pub balance_before: Uint128,
+}
+
+/// Protocol payout snapshotted by `Distribute`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedPayout {
+    pub protocol: String,
+    /// Whitelisted address the payout belongs to
+    pub address: Addr,
+    pub destination: Destination,
+    pub amount: Uint128,
+}
+
+/// IBC transfer of `amount` to a remote chain
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcTransfer {
+    pub channel_id: String,
+    pub remote_address: String,
+    pub timeout_seconds: u64,
+    pub amount: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WeightedBurnSink {
+    pub sink: Destination,
+    pub weight: Decimal,
+}
+
+/// Whitelist entries a cap applies to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapTarget {
+    /// All entries of the protocol
+    Protocol { protocol: String },
+    /// All entries with given address
+    Address { address: Addr },
+}
+
+impl CapTarget {
+    pub fn applies_to(&self, entry: &Whitelist) -> bool {
+        match self {
+            CapTarget::Protocol { protocol } => *protocol == entry.protocol,
+            CapTarget::Address { address } => *address == entry.address,
+        }
+    }
+
+    /// Storage key of the cap usage
+    pub fn key(&self) -> String {
+        match self {
+            CapTarget::Protocol { protocol } => format!("protocol:{}", protocol),
+            CapTarget::Address { address } => format!("address:{}", address),
+        }
+    }
+}
+
+/// Limits amount of `denom` paid out to `target`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RecipientCap {
+    pub target: CapTarget,
+    pub denom: String,
+    /// Maximum amount paid out by a single distribution
+    pub per_distribution: Option<Uint128>,
+    /// Maximum amount paid out within `Config::cap_window`
+    pub per_window: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnStrategy {
+    /// Send burn share to the burn sinks, or to `burn_address` if there are none
+    Direct {},
+    /// Swap burn share to `target_denom` on the `pair` and burn what is bought back
+    BuybackAndBurn { pair: Addr, target_denom: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapOverflow {
+    /// Add overflow to the burn share
+    Burn,
+    /// Split overflow between uncapped protocols proportionally to their shares.
+    /// Burned if every protocol is capped.
+    Resplit,
+}
+
+/// Payouts to a cap target within the last `Config::cap_window` seconds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct CapUsage {
+    pub payouts: Vec<CapPayout>,
+}
+
+impl CapUsage {
+    /// Total amount paid out within the window
+    pub fn received(&self) -> Uint128 {
+        self.payouts
+            .iter()
+            .fold(Uint128::zero(), |total, payout| total + payout.amount)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CapPayout {
+    pub time: Timestamp,
+    pub amount: Uint128,
+}
+
+/// Cached membership of the whitelist group
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GroupSnapshot {
+    pub group: Addr,
+    pub total_weight: u64,
+    /// Height of the last full refresh
+    pub height: u64,
+}
+
+/// Who can vote on weight proposals and what is needed to pass them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WeightVoting {
+    pub voters: VoterSet,
+    /// Time in seconds proposals are open for voting
+    pub voting_period: u64,
+    /// Part of total voting weight which has to vote yes to pass a proposal
+    pub threshold: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoterSet {
+    /// Fixed list of voters with their voting weights
+    Fixed { voters: Vec<Voter> },
+    /// Members of CW4 group contract, weighted as in the group
+    Group { contract: Addr },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Voter {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Vote {
+    Yes,
+    No,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Open,
+    /// Passed and applied to config
+    Passed,
+    Rejected,
+}
+
+/// Proposal to replace `weight_per_protocol`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WeightProposal {
+    pub id: u64,
+    pub proposer: Addr,
+    pub weights: Vec<WeightPerProtocol>,
+    /// Voting weights are taken at this height
+    pub start_height: u64,
+    pub expires: Timestamp,
+    /// Part of `total_weight` which has to vote yes to pass
+    pub threshold: Decimal,
+    /// Total weight of all voters at the proposal start
+    pub total_weight: u64,
+    pub yes: u64,
+    pub no: u64,
+    pub status: ProposalStatus,
+}
+
+impl WeightProposal {
+    pub fn is_passed(&self) -> bool {
+        self.total_weight > 0 && Decimal::from_ratio(self.yes, self.total_weight) >= self.threshold
+    }
+
+    /// Status at given time, open proposals are rejected once expired or once they cannot pass anymore
+    pub fn current_status(&self, time: Timestamp) -> ProposalStatus {
+        match self.status {
+            ProposalStatus::Open if time >= self.expires || !self.can_pass() => {
+                ProposalStatus::Rejected
+            }
+            ref status => status.clone(),
+        }
+    }
+
+    fn can_pass(&self) -> bool {
+        self.total_weight > 0
+            && Decimal::from_ratio(self.total_weight.saturating_sub(self.no), self.total_weight)
+                >= self.threshold
+    }
+}
+
+/// Vote of a voter with weight it had at the proposal start
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ballot {
+    pub vote: Vote,
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Request of a protocol to be added to the whitelist
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Application {
+    pub id: u64,
+    pub protocol: String,
+    /// Address to be whitelisted
+    pub address: Addr,
+    /// Address which submitted the application
+    pub applicant: Addr,
+    pub submitted_at: Timestamp,
+    pub status: ApplicationStatus,
+}
+
+/// Single config field modified by a revision, values are JSON encoded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl FieldChange {
+    pub fn new<T: Serialize>(field: &str, before: &T, after: &T) -> StdResult<Self> {
+        Ok(FieldChange {
+            field: field.to_owned(),
+            before: String::from_utf8(to_vec(before)?)?,
+            after: String::from_utf8(to_vec(after)?)?,
+        })
+    }
+}
+
+/// Entry of the append-only config history
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigRevision {
+    pub id: u64,
+    /// Address which sent the message changing config
+    pub sender: Addr,
+    /// Name of the action which changed config
+    pub action: String,
+    pub height: u64,
+    pub time: Timestamp,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Balance snapshot released in equal per-block portions over the epoch
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Epoch {
+    pub start_height: u64,
+    pub end_height: u64,
+    /// Balance snapshotted at the epoch start
+    pub amount: Uint128,
+    /// Part of `amount` already paid out
+    pub released: Uint128,
+}
+
+impl Epoch {
+    /// Part of `amount` unlocked at given height
+    pub fn unlocked(&self, height: u64) -> Uint128 {
+        if height >= self.end_height {
+            self.amount
+        } else {
+            self.amount.multiply_ratio(
+                height.saturating_sub(self.start_height),
+                self.end_height - self.start_height,
+            )
+        }
+    }
+
+    /// Unlocked amount which has not been paid out yet
+    pub fn releasable(&self, height: u64) -> Uint128 {
+        self.unlocked(height) - self.released
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.released >= self.amount
+    }
+}
+
+/// Cliff and linear vesting schedule
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    pub start: Timestamp,
+    /// Nothing is vested until `start + cliff` seconds
+    pub cliff: u64,
+    /// Everything is vested after `start + duration` seconds
+    pub duration: u64,
+}
+
+impl VestingSchedule {
+    /// Returns part of `total` vested at given time
+    pub fn vested(&self, total: Uint128, time: Timestamp) -> Uint128 {
+        if time < self.start.plus_seconds(self.cliff) {
+            return Uint128::zero();
+        }
+        let elapsed = time.seconds() - self.start.seconds();
+        if elapsed >= self.duration {
+            total
+        } else {
+            total.multiply_ratio(elapsed, self.duration)
+        }
+    }
+}
+
+/// Developer share accrued in given denom
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct VestingBalance {
+    pub accrued: Uint128,
+    pub claimed: Uint128,
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
+/// If set, paying out is stopped
+pub const PAUSED: Item<bool> = Item::new("paused");
+/// Pause set by governance, stops paying out as `PAUSED` does but only sudo can clear it
+pub const GOVERNANCE_PAUSED: Item<bool> = Item::new("governance_paused");
+pub const PENDING_CONFIG_CHANGE: Item<PendingConfigChange> = Item::new("pending_config_change");
+/// Number of stored config revisions, used as the next revision id
+pub const CONFIG_REVISION_COUNT: Item<u64> = Item::new("config_revision_count");
+pub const CONFIG_HISTORY: Map<U64Key, ConfigRevision> = Map::new("config_history");
+/// Current distribution epoch per denom
+pub const EPOCHS: Map<&str, Epoch> = Map::new("epochs");
+/// Vesting developer share per denom
+pub const DEVELOPER_VESTING: Map<&str, VestingBalance> = Map::new("developer_vesting");
+/// Number of whitelist applications so far, used as the next application id
+pub const APPLICATION_COUNT: Item<u64> = Item::new("application_count");
+pub const APPLICATIONS: Map<U64Key, Application> = Map::new("applications");
+pub const GROUP_SNAPSHOT: Item<GroupSnapshot> = Item::new("group_snapshot");
+/// Member weights of the group in `GROUP_SNAPSHOT`
+pub const GROUP_MEMBERS: Map<&Addr, u64> = Map::new("group_members");
+/// Number of weight proposals so far, used as the next proposal id
+pub const WEIGHT_PROPOSAL_COUNT: Item<u64> = Item::new("weight_proposal_count");
+pub const WEIGHT_PROPOSALS: Map<U64Key, WeightProposal> = Map::new("weight_proposals");
+pub const WEIGHT_BALLOTS: Map<(U64Key, &Addr), Ballot> = Map::new("weight_ballots");
+/// Address changes waiting for acceptance, keyed by the currently whitelisted address
+pub const PENDING_ADDRESS_CHANGES: Map<&Addr, Addr> = Map::new("pending_address_changes");
+/// Usage of recipient caps keyed by cap target and denom
+pub const CAP_USAGE: Map<(&str, &str), CapUsage> = Map::new("cap_usage");
+/// IBC transfers dispatched in current transaction, in order of their replies
+pub const IBC_TRANSFERS_IN_FLIGHT: Item<Vec<IbcTransfer>> = Item::new("ibc_transfers_in_flight");
+/// Number of escrowed IBC transfers so far, used as the next escrow id
+pub const IBC_ESCROW_COUNT: Item<u64> = Item::new("ibc_escrow_count");
+/// IBC transfers rejected on dispatch, waiting for retry
+pub const IBC_ESCROW: Map<U64Key, IbcTransfer> = Map::new("ibc_escrow");
+/// Total escrowed amount per denom
+pub const IBC_ESCROW_TOTAL: Map<&str, Uint128> = Map::new("ibc_escrow_total");
+/// IBC transfers accepted by the transfer module, keyed by source channel and packet sequence,
+/// until their acknowledgement or timeout is reported through sudo
+pub const IBC_PACKETS: Map<(&str, U64Key), IbcTransfer> = Map::new("ibc_packets");
+pub const PENDING_DISTRIBUTION: Item<PendingDistribution> = Item::new("pending_distribution");
+/// Swap to the payout denom dispatched in current transaction
+pub const PENDING_SWAP: Item<PendingSwap> = Item::new("pending_swap");
+/// Buyback of the burn share dispatched in current transaction
+pub const PENDING_BUYBACK: Item<PendingBuyback> = Item::new("pending_buyback");
+/// Payouts of `PENDING_DISTRIBUTION` keyed by their position in the queue
+pub const QUEUED_PAYOUTS: Map<U64Key, QueuedPayout> = Map::new("queued_payouts");
+
+pub struct WhitelistIndexes<'a> {
+    /// Whitelist entries of a protocol
+    pub protocol: MultiIndex<'a, (Vec<u8>, Vec<u8>), Whitelist>,
+}
+
+impl<'a> IndexList<Whitelist> for WhitelistIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Whitelist>> + '_> {
+        let v: Vec<&dyn Index<Whitelist>> = vec![&self.protocol];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Whitelist entries keyed by address
+pub fn whitelist<'a>() -> IndexedMap<'a, &'a Addr, Whitelist, WhitelistIndexes<'a>> {
+    let indexes = WhitelistIndexes {
+        protocol: MultiIndex::new(
+            |entry, pk| (entry.protocol.as_bytes().to_vec(), pk),
+            "whitelist",
+            "whitelist__protocol",
+        ),
+    };
+    IndexedMap::new("whitelist", indexes)
+}
+
+/// Weight of the protocol share per protocol
+pub const WEIGHTS: Map<&str, Decimal> = Map::new("weight_per_protocol");
+
+pub fn load_whitelist(storage: &dyn Storage) -> StdResult<Vec<Whitelist>> {
+    whitelist()
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect()
+}
+
+pub fn load_weights(storage: &dyn Storage) -> StdResult<Vec<WeightPerProtocol>> {
+    WEIGHTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (protocol, weight) = item?;
+            Ok(WeightPerProtocol {
+                protocol: String::from_utf8(protocol)?,
+                weight,
+            })
+        })
+        .collect()
+}
+
+/// Saves or removes whitelist entry of `address`. Returns the change if there is any.
+pub fn save_whitelist_entry(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    entry: Option<Whitelist>,
+) -> StdResult<Option<FieldChange>> {
+    let before = whitelist().may_load(storage, address)?;
+    if before == entry {
+        return Ok(None);
+    }
+    match &entry {
+        Some(entry) => whitelist().save(storage, address, entry)?,
+        None => whitelist().remove(storage, address)?,
+    }
+    FieldChange::new(&format!("whitelist.{}", address), &before, &entry).map(Some)
+}
+
+/// Saves or removes weight of `protocol`. Returns the change if there is any.
+pub fn save_weight(
+    storage: &mut dyn Storage,
+    protocol: &str,
+    weight: Option<Decimal>,
+) -> StdResult<Option<FieldChange>> {
+    let before = WEIGHTS.may_load(storage, protocol)?;
+    if before == weight {
+        return Ok(None);
+    }
+    match weight {
+        Some(weight) => WEIGHTS.save(storage, protocol, &weight)?,
+        None => WEIGHTS.remove(storage, protocol),
+    }
+    FieldChange::new(
+        &format!("weight_per_protocol.{}", protocol),
+        &before,
+        &weight,
+    )
+    .map(Some)
+}
+
+/// Replaces whole whitelist with `entries`. Returns changed entries.
+pub fn replace_whitelist(
+    storage: &mut dyn Storage,
+    entries: Vec<Whitelist>,
+) -> StdResult<Vec<FieldChange>> {
+    let mut changes = vec![];
+    for old in load_whitelist(storage)? {
+        if !entries.iter().any(|entry| entry.address == old.address) {
+            changes.extend(save_whitelist_entry(storage, &old.address, None)?);
+        }
+    }
+    for entry in entries {
+        let address = entry.address.clone();
+        changes.extend(save_whitelist_entry(storage, &address, Some(entry))?);
+    }
+    Ok(changes)
+}
+
+/// Replaces all weights with `weights`. Returns changed weights.
+pub fn replace_weights(
+    storage: &mut dyn Storage,
+    weights: Vec<WeightPerProtocol>,
+) -> StdResult<Vec<FieldChange>> {
+    let mut changes = vec![];
+    for old in load_weights(storage)? {
+        if !weights.iter().any(|wpp| wpp.protocol == old.protocol) {
+            changes.extend(save_weight(storage, &old.protocol, None)?);
+        }
+    }
+    for wpp in weights {
+        changes.extend(save_weight(storage, &wpp.protocol, Some(wpp.weight))?);
+    }
+    Ok(changes)
+}
+
+/// Config layout of v0.1.x, with whitelist and weights embedded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV0_1 {
+    pub admin: String,
+    pub burn_address: Addr,
+    pub developer_address: Addr,
+    pub whitelist: Vec<WhitelistV0_1>,
+    pub weight_per_protocol: Vec<WeightPerProtocol>,
+    pub percent_to_burn: Decimal,
+    pub percent_to_developer: Decimal,
+    pub percent_to_distribute: Decimal,
+    pub less_then_threshold: Uint128,
+}
+
+/// Whitelist entry layout of v0.1.x
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WhitelistV0_1 {
+    pub address: Addr,
+    pub protocol: String,
+}
+
+/// Reads config stored by v0.1.x, used by migration only
+pub const CONFIG_V0_1: Item<ConfigV0_1> = Item::new("config");
+
+/// Cumulative amount of `denom` deposited by `depositor` with `Deposit`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Contribution {
+    pub depositor: Addr,
+    pub denom: String,
+    pub deposited: Uint128,
+    /// Part of deposits assigned to burning by `percent_to_burn` at the time of deposit.
+    /// It is not necessarily burned in the end - config may change before distribution,
+    /// and deposits are not told apart from other balance once distributed.
+    pub assigned_to_burn: Uint128,
+}
+
+pub struct ContributionIndexes<'a> {
+    /// Contributions of a denom ordered by deposited amount
+    pub deposited: MultiIndex<'a, (Vec<u8>, U128Key, Vec<u8>), Contribution>,
+}
+
+impl<'a> IndexList<Contribution> for ContributionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Contribution>> + '_> {
+        let v: Vec<&dyn Index<Contribution>> = vec![&self.deposited];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Contributions keyed by depositor and denom
+pub fn contributions<'a>(
+) -> IndexedMap<'a, (&'a Addr, &'a str), Contribution, ContributionIndexes<'a>> {
+    let indexes = ContributionIndexes {
+        deposited: MultiIndex::new(
+            |contribution, pk| {
+                (
+                    contribution.denom.as_bytes().to_vec(),
+                    contribution.deposited.u128().into(),
+                    pk,
+                )
+            },
+            "contributions",
+            "contributions__deposited",
+        ),
+    };
+    IndexedMap::new("contributions", indexes)
+}